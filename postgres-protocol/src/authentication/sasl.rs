@@ -35,6 +35,20 @@ fn normalize(pass: &[u8]) -> Vec<u8> {
     }
 }
 
+fn nonce() -> String {
+    // rand 0.5's ThreadRng is cryptographically secure
+    let mut rng = rand::thread_rng();
+    (0..NONCE_LENGTH)
+        .map(|_| {
+            let mut v = rng.gen_range(0x21u8..0x7e);
+            if v == 0x2c {
+                v = 0x7e
+            }
+            v as char
+        })
+        .collect()
+}
+
 pub(crate) fn hi(str: &[u8], salt: &[u8], i: u32) -> [u8; 32] {
     let mut hmac =
         Hmac::<Sha256>::new_from_slice(str).expect("HMAC is able to accept all key sizes");
@@ -135,19 +149,7 @@ pub struct ScramSha256 {
 impl ScramSha256 {
     /// Constructs a new instance which will use the provided password for authentication.
     pub fn new(password: &[u8], channel_binding: ChannelBinding) -> ScramSha256 {
-        // rand 0.5's ThreadRng is cryptographically secure
-        let mut rng = rand::thread_rng();
-        let nonce = (0..NONCE_LENGTH)
-            .map(|_| {
-                let mut v = rng.gen_range(0x21u8..0x7e);
-                if v == 0x2c {
-                    v = 0x7e
-                }
-                v as char
-            })
-            .collect::<String>();
-
-        ScramSha256::new_inner(password, channel_binding, nonce)
+        ScramSha256::new_inner(password, channel_binding, nonce())
     }
 
     fn new_inner(password: &[u8], channel_binding: ChannelBinding, nonce: String) -> ScramSha256 {
@@ -288,6 +290,253 @@ impl ScramSha256 {
     }
 }
 
+#[cfg(any(test, feature = "test-util"))]
+enum ServerState {
+    Update {
+        nonce: String,
+    },
+    Finish {
+        gs2_header: String,
+        client_first_bare: String,
+        server_first: String,
+        nonce: String,
+    },
+    Done,
+}
+
+/// A type which handles the server side of the SCRAM-SHA-256/SCRAM-SHA-256-PLUS authentication
+/// process.
+///
+/// This is intended for testing client authentication against an in-process mock server, and
+/// requires the `test-util` Cargo feature.
+///
+/// The contents of the client's `SASLInitialResponse` message should be passed to the `update()`
+/// method, after which the buffer returned by the `message()` method should be sent to the client
+/// in an `AuthenticationSASLContinue` message.
+///
+/// The contents of the client's `SASLResponse` message should then be passed to the `finish()`
+/// method, after which the buffer returned by the `message()` method should be sent to the client
+/// in an `AuthenticationSASLFinal` message. If the client failed verification, `finish()` returns
+/// an error and the message contains the corresponding SCRAM `e=` server error instead.
+#[cfg(any(test, feature = "test-util"))]
+pub struct ScramSha256Server {
+    message: String,
+    salted_password: [u8; 32],
+    salt: Vec<u8>,
+    iterations: u32,
+    tls_server_end_point: Option<Vec<u8>>,
+    state: ServerState,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl ScramSha256Server {
+    /// Constructs a new instance which will verify clients against the provided password.
+    ///
+    /// If `tls_server_end_point` is provided, the server is assumed to have offered the
+    /// `SCRAM-SHA-256-PLUS` mechanism, and clients using channel binding must provide the same
+    /// `tls-server-end-point` data.
+    pub fn new(
+        password: &[u8],
+        salt: &[u8],
+        iterations: u32,
+        tls_server_end_point: Option<Vec<u8>>,
+    ) -> ScramSha256Server {
+        ScramSha256Server::new_inner(password, salt, iterations, tls_server_end_point, nonce())
+    }
+
+    fn new_inner(
+        password: &[u8],
+        salt: &[u8],
+        iterations: u32,
+        tls_server_end_point: Option<Vec<u8>>,
+        nonce: String,
+    ) -> ScramSha256Server {
+        ScramSha256Server {
+            message: String::new(),
+            salted_password: hi(&normalize(password), salt, iterations),
+            salt: salt.to_vec(),
+            iterations,
+            tls_server_end_point,
+            state: ServerState::Update { nonce },
+        }
+    }
+
+    /// Returns the message which should be sent to the client.
+    pub fn message(&self) -> &[u8] {
+        if let ServerState::Update { .. } = self.state {
+            panic!("invalid SCRAM state");
+        }
+        self.message.as_bytes()
+    }
+
+    /// Updates the state machine with the client-first message.
+    ///
+    /// This should be called when a `SASLInitialResponse` message is received.
+    pub fn update(&mut self, message: &[u8]) -> io::Result<()> {
+        let server_nonce = match mem::replace(&mut self.state, ServerState::Done) {
+            ServerState::Update { nonce } => nonce,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid SCRAM state",
+                ))
+            }
+        };
+
+        let message =
+            str::from_utf8(message).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let parsed = Parser::new(message).client_first_message()?;
+
+        match (parsed.channel_binding, &self.tls_server_end_point) {
+            (ClientChannelBinding::Unsupported, _) => {}
+            (ClientChannelBinding::Unrequested, None) => {}
+            (ClientChannelBinding::Unrequested, Some(_)) => {
+                return Err(scram_error("server-does-support-channel-binding"))
+            }
+            (ClientChannelBinding::Required("tls-server-end-point"), Some(_)) => {}
+            (ClientChannelBinding::Required(_), Some(_)) => {
+                return Err(scram_error("unsupported-channel-binding-type"))
+            }
+            (ClientChannelBinding::Required(_), None) => {
+                return Err(scram_error("channel-binding-not-supported"))
+            }
+        }
+
+        let nonce = format!("{}{}", parsed.nonce, server_nonce);
+
+        self.message.clear();
+        write!(
+            &mut self.message,
+            "r={},s={},i={}",
+            nonce,
+            Base64Display::new(&self.salt, &STANDARD),
+            self.iterations
+        )
+        .unwrap();
+
+        self.state = ServerState::Finish {
+            gs2_header: parsed.gs2_header.to_string(),
+            client_first_bare: parsed.bare.to_string(),
+            server_first: self.message.clone(),
+            nonce,
+        };
+        Ok(())
+    }
+
+    /// Verifies the client-final message.
+    ///
+    /// This should be called when a `SASLResponse` message is received. Authentication has only
+    /// succeeded if this method returns `Ok(())`.
+    pub fn finish(&mut self, message: &[u8]) -> io::Result<()> {
+        let (gs2_header, client_first_bare, server_first, nonce) =
+            match mem::replace(&mut self.state, ServerState::Done) {
+                ServerState::Finish {
+                    gs2_header,
+                    client_first_bare,
+                    server_first,
+                    nonce,
+                } => (gs2_header, client_first_bare, server_first, nonce),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid SCRAM state",
+                    ))
+                }
+            };
+
+        let message =
+            str::from_utf8(message).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let parsed = Parser::new(message).client_final_message()?;
+
+        let mut cbind_input = gs2_header.into_bytes();
+        if cbind_input.starts_with(b"p=") {
+            if let Some(data) = &self.tls_server_end_point {
+                cbind_input.extend(data);
+            }
+        }
+        if STANDARD.decode(parsed.channel_binding).ok() != Some(cbind_input) {
+            return self.error("channel-bindings-dont-match");
+        }
+
+        if parsed.nonce != nonce {
+            return self.error("other-error");
+        }
+
+        let proof = match STANDARD.decode(parsed.proof) {
+            Ok(proof) => proof,
+            Err(_) => return self.error("invalid-proof"),
+        };
+
+        let auth_message = format!(
+            "{},{},{}",
+            client_first_bare, server_first, parsed.without_proof
+        );
+
+        let mut hmac = Hmac::<Sha256>::new_from_slice(&self.salted_password)
+            .expect("HMAC is able to accept all key sizes");
+        hmac.update(b"Client Key");
+        let client_key = hmac.finalize().into_bytes();
+
+        let mut hash = Sha256::default();
+        hash.update(client_key.as_slice());
+        let stored_key = hash.finalize_fixed();
+
+        let mut hmac = Hmac::<Sha256>::new_from_slice(&stored_key)
+            .expect("HMAC is able to accept all key sizes");
+        hmac.update(auth_message.as_bytes());
+        let client_signature = hmac.finalize().into_bytes();
+
+        if proof.len() != client_signature.len() {
+            return self.error("invalid-proof");
+        }
+        let mut recovered_key = client_signature;
+        for (key, proof) in recovered_key.iter_mut().zip(proof) {
+            *key ^= proof;
+        }
+
+        let mut hash = Sha256::default();
+        hash.update(recovered_key.as_slice());
+        if hash.finalize_fixed() != stored_key {
+            return self.error("invalid-proof");
+        }
+
+        let mut hmac = Hmac::<Sha256>::new_from_slice(&self.salted_password)
+            .expect("HMAC is able to accept all key sizes");
+        hmac.update(b"Server Key");
+        let server_key = hmac.finalize().into_bytes();
+
+        let mut hmac = Hmac::<Sha256>::new_from_slice(&server_key)
+            .expect("HMAC is able to accept all key sizes");
+        hmac.update(auth_message.as_bytes());
+        let server_signature = hmac.finalize().into_bytes();
+
+        self.message.clear();
+        write!(
+            &mut self.message,
+            "v={}",
+            Base64Display::new(&server_signature, &STANDARD)
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    fn error(&mut self, error: &str) -> io::Result<()> {
+        self.message.clear();
+        write!(&mut self.message, "e={}", error).unwrap();
+        Err(scram_error(error))
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+fn scram_error(error: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("SCRAM error: {}", error),
+    )
+}
+
 struct Parser<'a> {
     s: &'a str,
     it: iter::Peekable<str::CharIndices<'a>>,
@@ -396,7 +645,7 @@ impl<'a> Parser<'a> {
     }
 
     fn value(&mut self) -> io::Result<&'a str> {
-        self.take_while(|c| matches!(c, '\0' | '=' | ','))
+        self.take_while(|c| !matches!(c, '\0' | '=' | ','))
     }
 
     fn server_error(&mut self) -> io::Result<Option<&'a str>> {
@@ -437,6 +686,99 @@ enum ServerFinalMessage<'a> {
     Verifier(&'a str),
 }
 
+#[cfg(any(test, feature = "test-util"))]
+impl<'a> Parser<'a> {
+    fn offset(&mut self) -> usize {
+        self.it.peek().map_or(self.s.len(), |&(i, _)| i)
+    }
+
+    fn client_channel_binding(&mut self) -> io::Result<ClientChannelBinding<'a>> {
+        match self.it.peek() {
+            Some(&(_, 'n')) => {
+                self.eat('n')?;
+                Ok(ClientChannelBinding::Unsupported)
+            }
+            Some(&(_, 'y')) => {
+                self.eat('y')?;
+                Ok(ClientChannelBinding::Unrequested)
+            }
+            _ => {
+                self.eat('p')?;
+                self.eat('=')?;
+                let name = self
+                    .take_while(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-'))?;
+                Ok(ClientChannelBinding::Required(name))
+            }
+        }
+    }
+
+    fn client_first_message(&mut self) -> io::Result<ClientFirstMessage<'a>> {
+        let channel_binding = self.client_channel_binding()?;
+        self.eat(',')?;
+        // Postgres doesn't support authorization identities
+        self.eat(',')?;
+        let bare_start = self.offset();
+        // Postgres ignores the username in favor of the one sent in the startup message
+        self.eat('n')?;
+        self.eat('=')?;
+        self.take_while(|c| c != ',')?;
+        self.eat(',')?;
+        let nonce = self.nonce()?;
+        self.eof()?;
+
+        Ok(ClientFirstMessage {
+            channel_binding,
+            gs2_header: &self.s[..bare_start],
+            bare: &self.s[bare_start..],
+            nonce,
+        })
+    }
+
+    fn client_final_message(&mut self) -> io::Result<ClientFinalMessage<'a>> {
+        self.eat('c')?;
+        self.eat('=')?;
+        let channel_binding = self.base64()?;
+        self.eat(',')?;
+        let nonce = self.nonce()?;
+        let proof_start = self.offset();
+        self.eat(',')?;
+        self.eat('p')?;
+        self.eat('=')?;
+        let proof = self.base64()?;
+        self.eof()?;
+
+        Ok(ClientFinalMessage {
+            channel_binding,
+            nonce,
+            without_proof: &self.s[..proof_start],
+            proof,
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+enum ClientChannelBinding<'a> {
+    Unsupported,
+    Unrequested,
+    Required(&'a str),
+}
+
+#[cfg(any(test, feature = "test-util"))]
+struct ClientFirstMessage<'a> {
+    channel_binding: ClientChannelBinding<'a>,
+    gs2_header: &'a str,
+    bare: &'a str,
+    nonce: &'a str,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+struct ClientFinalMessage<'a> {
+    channel_binding: &'a str,
+    nonce: &'a str,
+    without_proof: &'a str,
+    proof: &'a str,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -477,4 +819,139 @@ mod test {
 
         scram.finish(server_final.as_bytes()).unwrap();
     }
+
+    #[test]
+    fn parse_server_error() {
+        let message = Parser::new("e=invalid-proof")
+            .server_final_message()
+            .unwrap();
+        match message {
+            ServerFinalMessage::Error(e) => assert_eq!(e, "invalid-proof"),
+            ServerFinalMessage::Verifier(_) => panic!("expected an error"),
+        }
+    }
+
+    fn server_exchange(
+        password: &str,
+        server_password: &str,
+        client_binding: ChannelBinding,
+        tls_server_end_point: Option<Vec<u8>>,
+    ) -> (ScramSha256, ScramSha256Server) {
+        let mut scram = ScramSha256::new(password.as_bytes(), client_binding);
+        let mut server = ScramSha256Server::new(
+            server_password.as_bytes(),
+            b"0123456789abcdef",
+            4096,
+            tls_server_end_point,
+        );
+        server.update(scram.message()).unwrap();
+        scram.update(server.message()).unwrap();
+        (scram, server)
+    }
+
+    // recorded auth exchange from psql
+    #[test]
+    fn server_recorded_exchange() {
+        let client_first = "n,,n=,r=9IZ2O01zb9IgiIZ1WJ/zgpJB";
+        let server_first =
+            "r=9IZ2O01zb9IgiIZ1WJ/zgpJBjx/oIRLs02gGSHcw1KEty3eY,s=fs3IXBy7U7+IvVjZ,i\
+             =4096";
+        let client_final =
+            "c=biws,r=9IZ2O01zb9IgiIZ1WJ/zgpJBjx/oIRLs02gGSHcw1KEty3eY,p=AmNKosjJzS3\
+             1NTlQYNs5BTeQjdHdk7lOflDo5re2an8=";
+        let server_final = "v=U+ppxD5XUKtradnv8e2MkeupiA8FU87Sg8CXzXHDAzw=";
+
+        let mut server = ScramSha256Server::new_inner(
+            b"foobar",
+            &STANDARD.decode("fs3IXBy7U7+IvVjZ").unwrap(),
+            4096,
+            None,
+            "jx/oIRLs02gGSHcw1KEty3eY".to_string(),
+        );
+        server.update(client_first.as_bytes()).unwrap();
+        assert_eq!(str::from_utf8(server.message()).unwrap(), server_first);
+
+        server.finish(client_final.as_bytes()).unwrap();
+        assert_eq!(str::from_utf8(server.message()).unwrap(), server_final);
+    }
+
+    #[test]
+    fn server_exchange_without_channel_binding() {
+        let (mut scram, mut server) =
+            server_exchange("foobar", "foobar", ChannelBinding::unsupported(), None);
+        server.finish(scram.message()).unwrap();
+        scram.finish(server.message()).unwrap();
+    }
+
+    #[test]
+    fn server_exchange_with_channel_binding() {
+        let (mut scram, mut server) = server_exchange(
+            "foobar",
+            "foobar",
+            ChannelBinding::tls_server_end_point(b"signature".to_vec()),
+            Some(b"signature".to_vec()),
+        );
+        server.finish(scram.message()).unwrap();
+        scram.finish(server.message()).unwrap();
+    }
+
+    #[test]
+    fn server_channel_binding_mismatch() {
+        let (mut scram, mut server) = server_exchange(
+            "foobar",
+            "foobar",
+            ChannelBinding::tls_server_end_point(b"signature".to_vec()),
+            Some(b"other signature".to_vec()),
+        );
+        server.finish(scram.message()).unwrap_err();
+        assert_eq!(server.message(), b"e=channel-bindings-dont-match");
+
+        let err = scram.finish(server.message()).unwrap_err();
+        assert!(err.to_string().contains("channel-bindings-dont-match"));
+    }
+
+    #[test]
+    fn server_channel_binding_downgrade() {
+        let scram = ScramSha256::new(b"foobar", ChannelBinding::unrequested());
+        let mut server =
+            ScramSha256Server::new(b"foobar", b"salt", 4096, Some(b"signature".to_vec()));
+        let err = server.update(scram.message()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("server-does-support-channel-binding"));
+    }
+
+    #[test]
+    fn server_channel_binding_not_supported() {
+        let scram = ScramSha256::new(
+            b"foobar",
+            ChannelBinding::tls_server_end_point(b"signature".to_vec()),
+        );
+        let mut server = ScramSha256Server::new(b"foobar", b"salt", 4096, None);
+        let err = server.update(scram.message()).unwrap_err();
+        assert!(err.to_string().contains("channel-binding-not-supported"));
+    }
+
+    #[test]
+    fn server_invalid_proof() {
+        let (mut scram, mut server) =
+            server_exchange("foobar", "hunter2", ChannelBinding::unsupported(), None);
+        server.finish(scram.message()).unwrap_err();
+        assert_eq!(server.message(), b"e=invalid-proof");
+
+        let err = scram.finish(server.message()).unwrap_err();
+        assert!(err.to_string().contains("invalid-proof"));
+    }
+
+    #[test]
+    fn server_iteration_count() {
+        let mut scram = ScramSha256::new(b"foobar", ChannelBinding::unsupported());
+        let mut server = ScramSha256Server::new(b"foobar", b"salt", 1, None);
+        server.update(scram.message()).unwrap();
+        assert!(str::from_utf8(server.message()).unwrap().ends_with(",i=1"));
+
+        scram.update(server.message()).unwrap();
+        server.finish(scram.message()).unwrap();
+        scram.finish(server.message()).unwrap();
+    }
 }
@@ -0,0 +1,237 @@
+//! Hash functions compatible with Postgres's hash partitioning.
+//!
+//! Postgres routes a row to a hash partition by hashing each non-null column of the partition key with the "extended"
+//! hash function of the column's operator class and `HASH_PARTITION_SEED`, combining the results, and taking the
+//! combined hash modulo the partition modulus. `PartitionHasher` reproduces this so that the partition of a key can be
+//! computed without a round trip to the server, for example to route requests or to split a bulk load by partition.
+//!
+//! Postgres hashes byte strings in native-endian words, so every hash computed from bytes (including those of floats,
+//! `TEXT`, `BYTEA`, and `UUID` values) only matches that of a little-endian server. Hashes of integer types do not
+//! depend on the server's byte order.
+
+use crate::Oid;
+use byteorder::{ByteOrder, LittleEndian};
+
+#[cfg(test)]
+mod test;
+
+/// The seed Postgres uses when hashing partition keys.
+pub const HASH_PARTITION_SEED: u64 = 0x7A5B_2236_7996_DCFD;
+
+/// Computes the combined hash of the columns of a hash partition key.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PartitionHasher {
+    hash: u64,
+}
+
+impl PartitionHasher {
+    /// Creates a new hasher for a partition key.
+    pub fn new() -> PartitionHasher {
+        PartitionHasher::default()
+    }
+
+    /// Adds the hash of the next column of the partition key.
+    ///
+    /// The hash must be computed with `HASH_PARTITION_SEED`. Null columns do not contribute to the hash of a key and
+    /// should be skipped.
+    pub fn add(&mut self, hash: u64) -> &mut PartitionHasher {
+        self.hash = hash_combine(self.hash, hash);
+        self
+    }
+
+    /// Returns the combined hash of the columns added so far.
+    pub fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the remainder of the partition containing the key, given the modulus of the table's partitions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn remainder(&self, modulus: u64) -> u64 {
+        self.hash % modulus
+    }
+}
+
+/// Combines two 64 bit hashes.
+///
+/// Equivalent to Postgres's `hash_combine64`.
+#[inline]
+pub fn hash_combine(a: u64, b: u64) -> u64 {
+    a ^ b
+        .wrapping_add(0x49a0_f4dd_15e5_a8e3)
+        .wrapping_add(a << 54)
+        .wrapping_add(a >> 7)
+}
+
+/// Hashes a `BOOL` value.
+///
+/// Equivalent to Postgres's `hashcharextended`, which is used for `BOOL` and `"char"` values.
+#[inline]
+pub fn bool_hash(v: bool, seed: u64) -> u64 {
+    hash_uint32_extended(v as u32, seed)
+}
+
+/// Hashes an `INT2` value.
+///
+/// Equivalent to Postgres's `hashint2extended`.
+#[inline]
+pub fn int2_hash(v: i16, seed: u64) -> u64 {
+    hash_uint32_extended(i32::from(v) as u32, seed)
+}
+
+/// Hashes an `INT4` value.
+///
+/// Equivalent to Postgres's `hashint4extended`, which is also used for `DATE` values.
+#[inline]
+pub fn int4_hash(v: i32, seed: u64) -> u64 {
+    hash_uint32_extended(v as u32, seed)
+}
+
+/// Hashes an `INT8` value.
+///
+/// Equivalent to Postgres's `hashint8extended`, which is also used for `TIMESTAMP` and `TIMESTAMPTZ` values.
+#[inline]
+pub fn int8_hash(v: i64, seed: u64) -> u64 {
+    let lohalf = v as u32;
+    let hihalf = (v >> 32) as u32;
+    let lohalf = lohalf ^ if v >= 0 { hihalf } else { !hihalf };
+    hash_uint32_extended(lohalf, seed)
+}
+
+/// Hashes an `OID` value.
+///
+/// Equivalent to Postgres's `hashoidextended`.
+#[inline]
+pub fn oid_hash(v: Oid, seed: u64) -> u64 {
+    hash_uint32_extended(v, seed)
+}
+
+/// Hashes a `FLOAT4` value.
+///
+/// Equivalent to Postgres's `hashfloat4extended`.
+#[inline]
+pub fn float4_hash(v: f32, seed: u64) -> u64 {
+    float8_hash(f64::from(v), seed)
+}
+
+/// Hashes a `FLOAT8` value.
+///
+/// Equivalent to Postgres's `hashfloat8extended`.
+pub fn float8_hash(v: f64, seed: u64) -> u64 {
+    // positive and negative zero must hash the same, as must all NaNs
+    if v == 0. {
+        return seed;
+    }
+    let v = if v.is_nan() { f64::NAN } else { v };
+    hash_bytes_extended(&v.to_le_bytes(), seed)
+}
+
+/// Hashes a `TEXT` or `VARCHAR` value.
+///
+/// Equivalent to Postgres's `hashtextextended` for deterministic collations.
+#[inline]
+pub fn text_hash(v: &str, seed: u64) -> u64 {
+    hash_bytes_extended(v.as_bytes(), seed)
+}
+
+/// Hashes a `BYTEA` value.
+///
+/// Equivalent to Postgres's `hashvarlenaextended`.
+#[inline]
+pub fn bytea_hash(v: &[u8], seed: u64) -> u64 {
+    hash_bytes_extended(v, seed)
+}
+
+/// Hashes a `UUID` value.
+///
+/// Equivalent to Postgres's `uuid_hash_extended`.
+#[inline]
+pub fn uuid_hash(v: [u8; 16], seed: u64) -> u64 {
+    hash_bytes_extended(&v, seed)
+}
+
+/// Hashes a 32 bit value.
+///
+/// Equivalent to Postgres's `hash_uint32_extended`.
+pub fn hash_uint32_extended(k: u32, seed: u64) -> u64 {
+    let mut a = 0x9e37_79b9_u32.wrapping_add(4).wrapping_add(3_923_095);
+    let mut b = a;
+    let mut c = a;
+
+    if seed != 0 {
+        a = a.wrapping_add((seed >> 32) as u32);
+        b = b.wrapping_add(seed as u32);
+        mix(&mut a, &mut b, &mut c);
+    }
+
+    a = a.wrapping_add(k);
+    finish(&mut a, &mut b, &mut c);
+
+    (u64::from(b) << 32) | u64::from(c)
+}
+
+/// Hashes a sequence of bytes.
+///
+/// Equivalent to Postgres's `hash_bytes_extended` (also known as `hash_any_extended`), which is an implementation of
+/// Bob Jenkins's lookup3 hash, as computed on little-endian servers.
+pub fn hash_bytes_extended(mut k: &[u8], seed: u64) -> u64 {
+    let mut a = 0x9e37_79b9_u32
+        .wrapping_add(k.len() as u32)
+        .wrapping_add(3_923_095);
+    let mut b = a;
+    let mut c = a;
+
+    if seed != 0 {
+        // the seed is treated as a 12 byte chunk of data, padded with four bytes of zeroes
+        a = a.wrapping_add((seed >> 32) as u32);
+        b = b.wrapping_add(seed as u32);
+        mix(&mut a, &mut b, &mut c);
+    }
+
+    while k.len() >= 12 {
+        a = a.wrapping_add(LittleEndian::read_u32(&k[0..]));
+        b = b.wrapping_add(LittleEndian::read_u32(&k[4..]));
+        c = c.wrapping_add(LittleEndian::read_u32(&k[8..]));
+        mix(&mut a, &mut b, &mut c);
+        k = &k[12..];
+    }
+
+    let mut tail = [0; 12];
+    tail[..k.len()].copy_from_slice(k);
+    a = a.wrapping_add(LittleEndian::read_u32(&tail[0..]));
+    b = b.wrapping_add(LittleEndian::read_u32(&tail[4..]));
+    // the lowest byte of c is reserved for the length
+    c = c.wrapping_add(LittleEndian::read_u32(&tail[8..]) << 8);
+    finish(&mut a, &mut b, &mut c);
+
+    (u64::from(b) << 32) | u64::from(c)
+}
+
+#[inline]
+fn mix(a: &mut u32, b: &mut u32, c: &mut u32) {
+    *a = a.wrapping_sub(*c) ^ c.rotate_left(4);
+    *c = c.wrapping_add(*b);
+    *b = b.wrapping_sub(*a) ^ a.rotate_left(6);
+    *a = a.wrapping_add(*c);
+    *c = c.wrapping_sub(*b) ^ b.rotate_left(8);
+    *b = b.wrapping_add(*a);
+    *a = a.wrapping_sub(*c) ^ c.rotate_left(16);
+    *c = c.wrapping_add(*b);
+    *b = b.wrapping_sub(*a) ^ a.rotate_left(19);
+    *a = a.wrapping_add(*c);
+    *c = c.wrapping_sub(*b) ^ b.rotate_left(4);
+    *b = b.wrapping_add(*a);
+}
+
+#[inline]
+fn finish(a: &mut u32, b: &mut u32, c: &mut u32) {
+    *c = (*c ^ *b).wrapping_sub(b.rotate_left(14));
+    *a = (*a ^ *c).wrapping_sub(c.rotate_left(11));
+    *b = (*b ^ *a).wrapping_sub(a.rotate_left(25));
+    *c = (*c ^ *b).wrapping_sub(b.rotate_left(16));
+    *a = (*a ^ *c).wrapping_sub(c.rotate_left(4));
+    *b = (*b ^ *a).wrapping_sub(a.rotate_left(14));
+    *c = (*c ^ *b).wrapping_sub(b.rotate_left(24));
+}
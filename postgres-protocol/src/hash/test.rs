@@ -0,0 +1,130 @@
+use super::*;
+
+// Expected values are taken from the output of the corresponding functions on a Postgres server.
+
+#[test]
+fn int8() {
+    let cases = [
+        (0, 4154612158245552303, -4403592609991167795),
+        (1, -3670598878359251130, 5968994663651403477),
+        (-1, -1888257769727981238, -5017072347659237694),
+        (42, 8010225493015854792, 7363975540656877951),
+        (i64::MAX, 1977219185673256887, 4938542303000433043),
+        (i64::MIN, -9109110466470504941, -6050265599104649060),
+    ];
+
+    for (v, unseeded, seeded) in cases {
+        assert_eq!(int8_hash(v, 0) as i64, unseeded, "{}", v);
+        assert_eq!(int8_hash(v, HASH_PARTITION_SEED) as i64, seeded, "{}", v);
+    }
+}
+
+#[test]
+fn int4_int2() {
+    let cases = [
+        (0, -4403592609991167795),
+        (1, 5968994663651403477),
+        (-1, -5017072347659237694),
+        (42, 7363975540656877951),
+    ];
+
+    for (v, seeded) in cases {
+        assert_eq!(int4_hash(v, HASH_PARTITION_SEED) as i64, seeded, "{}", v);
+        assert_eq!(
+            int2_hash(v as i16, HASH_PARTITION_SEED) as i64,
+            seeded,
+            "{}",
+            v
+        );
+    }
+}
+
+#[test]
+fn text() {
+    let cases = [
+        ("", -6939563903564495251, -5700645584453517373),
+        ("a", 3591986179850072241, -6705225459120232837),
+        ("abc", -6747756470228489321, 3628778498291917250),
+        ("hello world", 6947366892317592103, 7664296498819024037),
+        ("twelve bytes", -7123776401673968163, 1551002018851372083),
+        ("thirteen bytes", 2247937448126379923, 7712292633767806962),
+        (
+            "héllo wörld, this is longer than 24",
+            4794542847848370409,
+            -5672345682576523182,
+        ),
+    ];
+
+    for (v, unseeded, seeded) in cases {
+        assert_eq!(text_hash(v, 0) as i64, unseeded, "{:?}", v);
+        assert_eq!(text_hash(v, HASH_PARTITION_SEED) as i64, seeded, "{:?}", v);
+    }
+}
+
+#[test]
+fn float() {
+    let cases = [
+        (0., HASH_PARTITION_SEED as i64),
+        (-0., HASH_PARTITION_SEED as i64),
+        (1.5, -2826383321045882978),
+        (-2.25, -7786489153844000670),
+        (f64::NAN, -6475869854796787835),
+        (-f64::NAN, -6475869854796787835),
+        (f64::INFINITY, -4970436883654098436),
+    ];
+
+    for (v, seeded) in cases {
+        assert_eq!(float8_hash(v, HASH_PARTITION_SEED) as i64, seeded, "{}", v);
+    }
+    assert_eq!(
+        float4_hash(1.5, HASH_PARTITION_SEED) as i64,
+        -2826383321045882978
+    );
+}
+
+#[test]
+fn other() {
+    assert_eq!(
+        bool_hash(true, HASH_PARTITION_SEED) as i64,
+        5968994663651403477
+    );
+    assert_eq!(
+        oid_hash(12345, HASH_PARTITION_SEED) as i64,
+        3476363059597467753
+    );
+    let uuid = [
+        0xa0, 0xee, 0xbc, 0x99, 0x9c, 0x0b, 0x4e, 0xf8, 0xbb, 0x6d, 0x6b, 0xb9, 0xbd, 0x38, 0x0a,
+        0x11,
+    ];
+    assert_eq!(
+        uuid_hash(uuid, HASH_PARTITION_SEED) as i64,
+        424513190990560493
+    );
+}
+
+#[test]
+fn partition() {
+    // checked with `satisfies_hash_partition` against a table partitioned by `HASH (a INT8, b TEXT)`
+    let remainder = |a: i64, b: Option<&str>| {
+        let mut hasher = PartitionHasher::new();
+        hasher.add(int8_hash(a, HASH_PARTITION_SEED));
+        if let Some(b) = b {
+            hasher.add(text_hash(b, HASH_PARTITION_SEED));
+        }
+        hasher.remainder(7)
+    };
+
+    assert_eq!(remainder(42, Some("hello world")), 5);
+    assert_eq!(remainder(42, None), 0);
+    assert_eq!(remainder(-1, Some("abc")), 6);
+
+    // and a table partitioned by `HASH (c BYTEA)`
+    let remainder = |c: &[u8]| {
+        PartitionHasher::new()
+            .add(bytea_hash(c, HASH_PARTITION_SEED))
+            .remainder(100)
+    };
+
+    assert_eq!(remainder(b"\x00\xff\x10"), 61);
+    assert_eq!(remainder(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]), 97);
+}
@@ -17,8 +17,10 @@ use std::io;
 
 pub mod authentication;
 pub mod escape;
+pub mod hash;
 pub mod message;
 pub mod password;
+pub mod text;
 pub mod types;
 
 /// A Postgres OID.
@@ -0,0 +1,656 @@
+//! Parsers for the text representations of Postgres values.
+//!
+//! Values are sent in their text representation by the simple query protocol and by `COPY` in its `TEXT` and `CSV`
+//! formats. These parsers follow the server's own input rules rather than splitting on delimiters, so quoted and
+//! escaped values are handled correctly, and they do not depend on the client's locale.
+//!
+//! Date and time values are expected to be in the `ISO` `DateStyle`, which is the server's default.
+
+use crate::types::ArrayDimension;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::str;
+
+#[cfg(test)]
+mod test;
+
+// The server's maximum number of array dimensions.
+const MAX_ARRAY_DIMENSIONS: usize = 6;
+
+const USECS_PER_SEC: i64 = 1_000_000;
+const USECS_PER_DAY: i64 = 86_400 * USECS_PER_SEC;
+
+// The number of days between 1970-01-01 and 2000-01-01.
+const UNIX_EPOCH_TO_POSTGRES_EPOCH_DAYS: i64 = 10_957;
+
+/// Parses a `BOOL` value.
+///
+/// Like the server, this accepts `true`, `yes`, `on`, and `1` as well as `false`, `no`, `off`, and `0`, along with any
+/// unambiguous prefix of them, ignoring case and surrounding whitespace.
+pub fn bool_from_text(s: &str) -> Result<bool, Box<dyn Error + Sync + Send>> {
+    let v = s.trim_matches(is_space).to_ascii_lowercase();
+    let prefix_of = |word: &str, min_len: usize| v.len() >= min_len && word.starts_with(&v);
+
+    if prefix_of("true", 1) || prefix_of("yes", 1) || prefix_of("on", 2) || v == "1" {
+        Ok(true)
+    } else if prefix_of("false", 1) || prefix_of("no", 1) || prefix_of("off", 2) || v == "0" {
+        Ok(false)
+    } else {
+        Err(format!("invalid input syntax for type boolean: {:?}", s).into())
+    }
+}
+
+/// Parses a `BYTEA` value in either the `hex` or `escape` format.
+pub fn bytea_from_text(s: &str) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>> {
+    let s = s.as_bytes();
+
+    if let Some(hex) = s.strip_prefix(b"\\x") {
+        let mut out = Vec::with_capacity(hex.len() / 2);
+        let mut i = 0;
+        while i < hex.len() {
+            // whitespace is allowed between, but not within, pairs of digits
+            if matches!(hex[i], b' ' | b'\t' | b'\n' | b'\r') {
+                i += 1;
+                continue;
+            }
+            let lo = match hex.get(i + 1) {
+                Some(&lo) => lo,
+                None => return Err("invalid hexadecimal data: odd number of digits".into()),
+            };
+            out.push(hex_digit(hex[i])? << 4 | hex_digit(lo)?);
+            i += 2;
+        }
+        return Ok(out);
+    }
+
+    let mut out = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] != b'\\' {
+            out.push(s[i]);
+            i += 1;
+        } else if s.get(i + 1) == Some(&b'\\') {
+            out.push(b'\\');
+            i += 2;
+        } else if let Some(&[a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7']) =
+            s.get(i + 1..i + 4)
+        {
+            out.push((a - b'0') << 6 | (b - b'0') << 3 | (c - b'0'));
+            i += 4;
+        } else {
+            return Err("invalid input syntax for type bytea".into());
+        }
+    }
+    Ok(out)
+}
+
+fn hex_digit(b: u8) -> Result<u8, Box<dyn Error + Sync + Send>> {
+    match b {
+        b'0'..=b'9' => Ok(b - b'0'),
+        b'a'..=b'f' => Ok(b - b'a' + 10),
+        b'A'..=b'F' => Ok(b - b'A' + 10),
+        _ => Err(format!("invalid hexadecimal digit: {:?}", char::from(b)).into()),
+    }
+}
+
+/// An array parsed from its text representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextArray {
+    dimensions: Vec<ArrayDimension>,
+    elements: Vec<Option<String>>,
+}
+
+impl TextArray {
+    /// Returns the dimensions of the array.
+    ///
+    /// An empty array has no dimensions.
+    pub fn dimensions(&self) -> &[ArrayDimension] {
+        &self.dimensions
+    }
+
+    /// Returns the elements of the array in row-major order.
+    ///
+    /// Elements are in the text representation of the array's element type, and are `None` if they are `NULL`.
+    pub fn elements(&self) -> &[Option<String>] {
+        &self.elements
+    }
+
+    /// Consumes the array, returning its elements in row-major order.
+    pub fn into_elements(self) -> Vec<Option<String>> {
+        self.elements
+    }
+}
+
+/// Parses an array value.
+///
+/// `delimiter` is the character separating elements of the array, which is `,` for every built-in type other than
+/// `BOX`, which uses `;`.
+pub fn array_from_text(s: &str, delimiter: u8) -> Result<TextArray, Box<dyn Error + Sync + Send>> {
+    let mut parser = Parser::new(s);
+    parser.skip_array_space();
+
+    let mut bounds = vec![];
+    while parser.eat(b'[') {
+        let first = parser.int()?;
+        let (lower, upper) = if parser.eat(b':') {
+            (first, parser.int()?)
+        } else {
+            (1, first)
+        };
+        if !parser.eat(b']') {
+            return Err(parser.error("missing \"]\" in array dimensions"));
+        }
+        bounds.push((lower, upper));
+        parser.skip_array_space();
+    }
+    if !bounds.is_empty() {
+        if !parser.eat(b'=') {
+            return Err(parser.error("missing \"=\" after array dimensions"));
+        }
+        parser.skip_array_space();
+    }
+
+    if !parser.eat(b'{') {
+        return Err(parser.error("array value must start with \"{\" or dimension information"));
+    }
+    let mut array = ArrayParser {
+        parser,
+        delimiter,
+        lens: vec![],
+        leaf_depth: None,
+        elements: vec![],
+    };
+    array.level(0)?;
+    let mut parser = array.parser;
+    parser.skip_array_space();
+    if !parser.at_end() {
+        return Err(parser.error("junk after closing right brace"));
+    }
+
+    let dimensions = if bounds.is_empty() {
+        array
+            .lens
+            .iter()
+            .map(|&len| ArrayDimension {
+                len,
+                lower_bound: 1,
+            })
+            .collect()
+    } else {
+        if bounds.len() != array.lens.len()
+            || bounds
+                .iter()
+                .zip(&array.lens)
+                .any(|(&(lower, upper), &len)| {
+                    i64::from(upper) - i64::from(lower) + 1 != i64::from(len)
+                })
+        {
+            return Err("specified array dimensions do not match array contents".into());
+        }
+        bounds
+            .iter()
+            .zip(&array.lens)
+            .map(|(&(lower_bound, _), &len)| ArrayDimension { len, lower_bound })
+            .collect()
+    };
+
+    Ok(TextArray {
+        dimensions,
+        elements: array.elements,
+    })
+}
+
+struct ArrayParser<'a> {
+    parser: Parser<'a>,
+    delimiter: u8,
+    /// The length of each dimension, once known.
+    lens: Vec<i32>,
+    /// The depth at which elements appear, once known.
+    leaf_depth: Option<usize>,
+    elements: Vec<Option<String>>,
+}
+
+impl ArrayParser<'_> {
+    // Parses the contents of a level of braces, after its opening brace.
+    fn level(&mut self, depth: usize) -> Result<(), Box<dyn Error + Sync + Send>> {
+        if depth >= MAX_ARRAY_DIMENSIONS {
+            return Err(format!(
+                "number of array dimensions exceeds the maximum allowed ({})",
+                MAX_ARRAY_DIMENSIONS
+            )
+            .into());
+        }
+
+        self.parser.skip_array_space();
+        if self.parser.eat(b'}') {
+            if depth == 0 {
+                return Ok(());
+            }
+            return Err(self.parser.error("unexpected \"}\" character"));
+        }
+
+        let nested = self.parser.peek() == Some(b'{');
+        let mut len = 0;
+        loop {
+            self.parser.skip_array_space();
+            if nested {
+                if !self.parser.eat(b'{') {
+                    return Err(self.mismatched());
+                }
+                self.level(depth + 1)?;
+            } else {
+                let element = self.element()?;
+                self.elements.push(element);
+            }
+            len += 1;
+
+            self.parser.skip_array_space();
+            match self.parser.bump() {
+                Some(b) if b == self.delimiter => {}
+                Some(b'}') => break,
+                Some(_) => return Err(self.parser.error("unexpected character")),
+                None => return Err(self.parser.error("unexpected end of input")),
+            }
+        }
+
+        if !nested {
+            match self.leaf_depth {
+                Some(leaf_depth) if leaf_depth != depth => return Err(self.mismatched()),
+                _ => self.leaf_depth = Some(depth),
+            }
+        }
+        if self.lens.len() <= depth {
+            self.lens.resize(depth + 1, -1);
+        }
+        match self.lens[depth] {
+            -1 => self.lens[depth] = len,
+            expected if expected != len => return Err(self.mismatched()),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn element(&mut self) -> Result<Option<String>, Box<dyn Error + Sync + Send>> {
+        let mut out = vec![];
+
+        if self.parser.eat(b'"') {
+            loop {
+                match self.parser.bump() {
+                    Some(b'"') => break,
+                    Some(b'\\') => out.push(self.parser.escaped()?),
+                    Some(b) => out.push(b),
+                    None => return Err(self.parser.error("unexpected end of input")),
+                }
+            }
+            return Ok(Some(String::from_utf8(out)?));
+        }
+
+        // trailing whitespace is not part of an unquoted element unless it's escaped
+        let mut len = 0;
+        let mut escaped = false;
+        loop {
+            match self.parser.peek() {
+                Some(b) if b == self.delimiter || b == b'}' => break,
+                Some(b'\\') => {
+                    self.parser.pos += 1;
+                    out.push(self.parser.escaped()?);
+                    len = out.len();
+                    escaped = true;
+                }
+                Some(b'"' | b'{') => return Err(self.parser.error("unexpected character")),
+                Some(b) => {
+                    self.parser.pos += 1;
+                    out.push(b);
+                    if !is_array_space(b) {
+                        len = out.len();
+                    }
+                }
+                None => return Err(self.parser.error("unexpected end of input")),
+            }
+        }
+        out.truncate(len);
+
+        if out.is_empty() {
+            return Err(self.parser.error("unexpected character"));
+        }
+        if !escaped && out.eq_ignore_ascii_case(b"NULL") {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8(out)?))
+    }
+
+    fn mismatched(&self) -> Box<dyn Error + Sync + Send> {
+        self.parser
+            .error("multidimensional arrays must have sub-arrays with matching dimensions")
+    }
+}
+
+/// Parses a composite (row) value.
+///
+/// Fields are returned in the text representations of their types, and are `None` if they are `NULL`. The text
+/// representation of a row with no fields is the same as that of a row with a single `NULL` field, so `()` is parsed
+/// as the latter.
+pub fn composite_from_text(s: &str) -> Result<Vec<Option<String>>, Box<dyn Error + Sync + Send>> {
+    let mut parser = Parser::new(s);
+    parser.skip_space();
+    if !parser.eat(b'(') {
+        return Err(parser.error("missing left parenthesis"));
+    }
+
+    let mut fields = vec![];
+    loop {
+        // an empty field is NULL, while an empty string is written as `""`
+        if matches!(parser.peek(), Some(b',' | b')')) {
+            fields.push(None);
+        } else {
+            let mut out = vec![];
+            let mut quoted = false;
+            loop {
+                match parser.peek() {
+                    Some(b',' | b')') if !quoted => break,
+                    Some(b'\\') => {
+                        parser.pos += 1;
+                        out.push(parser.escaped()?);
+                    }
+                    Some(b'"') => {
+                        parser.pos += 1;
+                        if quoted && parser.peek() == Some(b'"') {
+                            parser.pos += 1;
+                            out.push(b'"');
+                        } else {
+                            quoted = !quoted;
+                        }
+                    }
+                    Some(b) => {
+                        parser.pos += 1;
+                        out.push(b);
+                    }
+                    None => return Err(parser.error("unexpected end of input")),
+                }
+            }
+            fields.push(Some(String::from_utf8(out)?));
+        }
+
+        if parser.eat(b')') {
+            break;
+        }
+        parser.pos += 1;
+    }
+
+    parser.skip_space();
+    if !parser.at_end() {
+        return Err(parser.error("junk after right parenthesis"));
+    }
+    Ok(fields)
+}
+
+/// Parses a `DATE` value, returning the number of days since January 1st, 2000.
+///
+/// `infinity` and `-infinity` are returned as `i32::MAX` and `i32::MIN`, as they are in the binary format.
+pub fn date_from_text(s: &str) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    match s {
+        "infinity" => return Ok(i32::MAX),
+        "-infinity" => return Ok(i32::MIN),
+        _ => {}
+    }
+
+    let mut parser = Parser::new(s);
+    let date = parser.date()?;
+    let days = parser.era(date)?;
+    if !parser.at_end() {
+        return Err(parser.error("invalid input syntax for type date"));
+    }
+
+    i32::try_from(days)
+        .ok()
+        .filter(|days| *days != i32::MAX && *days != i32::MIN)
+        .ok_or_else(|| "date out of range".into())
+}
+
+/// Parses a `TIMESTAMP` value, returning the number of microseconds since midnight on January 1st, 2000.
+///
+/// `infinity` and `-infinity` are returned as `i64::MAX` and `i64::MIN`, as they are in the binary format.
+pub fn timestamp_from_text(s: &str) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    timestamp(s, false)
+}
+
+/// Parses a `TIMESTAMPTZ` value, returning the number of microseconds since midnight UTC on January 1st, 2000.
+///
+/// The value must include its UTC offset, as it does when output by the server. `infinity` and `-infinity` are
+/// returned as `i64::MAX` and `i64::MIN`, as they are in the binary format.
+pub fn timestamptz_from_text(s: &str) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    timestamp(s, true)
+}
+
+fn timestamp(s: &str, with_offset: bool) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    match s {
+        "infinity" => return Ok(i64::MAX),
+        "-infinity" => return Ok(i64::MIN),
+        _ => {}
+    }
+
+    let mut parser = Parser::new(s);
+    let date = parser.date()?;
+    if !parser.eat(b' ') && !parser.eat(b'T') {
+        return Err(parser.error("expected a time"));
+    }
+    let time = parser.time()?;
+    let offset = if with_offset { parser.offset()? } else { 0 };
+    let days = parser.era(date)?;
+    if !parser.at_end() {
+        return Err(parser.error("invalid input syntax for type timestamp"));
+    }
+
+    days.checked_mul(USECS_PER_DAY)
+        .and_then(|v| v.checked_add(time))
+        .and_then(|v| v.checked_sub(offset * USECS_PER_SEC))
+        .filter(|v| *v != i64::MAX && *v != i64::MIN)
+        .ok_or_else(|| "timestamp out of range".into())
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Parser<'a> {
+        Parser {
+            s: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, message: &str) -> Box<dyn Error + Sync + Send> {
+        format!("{} at position {}", message, self.pos).into()
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos == self.s.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek().is_some_and(|b| is_space(char::from(b))) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_array_space(&mut self) {
+        while self.peek().is_some_and(is_array_space) {
+            self.pos += 1;
+        }
+    }
+
+    // Returns the byte following a backslash.
+    fn escaped(&mut self) -> Result<u8, Box<dyn Error + Sync + Send>> {
+        self.bump()
+            .ok_or_else(|| self.error("unexpected end of input"))
+    }
+
+    fn digits(&mut self, min: usize, max: usize) -> Result<&'a str, Box<dyn Error + Sync + Send>> {
+        let start = self.pos;
+        while self.pos - start < max && self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos - start < min {
+            return Err(self.error("expected a digit"));
+        }
+        // only ASCII digits were consumed
+        Ok(str::from_utf8(&self.s[start..self.pos]).unwrap())
+    }
+
+    fn int(&mut self) -> Result<i32, Box<dyn Error + Sync + Send>> {
+        let negative = self.eat(b'-');
+        if !negative {
+            self.eat(b'+');
+        }
+        let digits = self.digits(1, usize::MAX)?;
+        let v = if negative {
+            format!("-{}", digits).parse()?
+        } else {
+            digits.parse()?
+        };
+        Ok(v)
+    }
+
+    fn number(&mut self, len: usize, max: u32) -> Result<u32, Box<dyn Error + Sync + Send>> {
+        let v = self.digits(len, len)?.parse()?;
+        if v > max {
+            return Err(self.error("field value out of range"));
+        }
+        Ok(v)
+    }
+
+    fn date(&mut self) -> Result<(i64, u32, u32), Box<dyn Error + Sync + Send>> {
+        let year = self.digits(4, 9)?.parse()?;
+        if !self.eat(b'-') {
+            return Err(self.error("expected \"-\""));
+        }
+        let month = self.number(2, 12)?;
+        if !self.eat(b'-') {
+            return Err(self.error("expected \"-\""));
+        }
+        let day = self.number(2, 31)?;
+        Ok((year, month, day))
+    }
+
+    // Consumes an optional ` BC` suffix, and returns the number of days between 2000-01-01 and the date.
+    fn era(
+        &mut self,
+        (year, month, day): (i64, u32, u32),
+    ) -> Result<i64, Box<dyn Error + Sync + Send>> {
+        // there is no year 0, so 1 BC is the astronomical year 0
+        if year == 0 {
+            return Err("date/time field value out of range".into());
+        }
+        let year = if self.s[self.pos..] == *b" BC" {
+            self.pos += 3;
+            1 - year
+        } else {
+            year
+        };
+
+        if month == 0 || day == 0 || day > days_in_month(year, month) {
+            return Err("date/time field value out of range".into());
+        }
+
+        Ok(days_from_civil(year, month, day) - UNIX_EPOCH_TO_POSTGRES_EPOCH_DAYS)
+    }
+
+    // Returns the number of microseconds since midnight.
+    fn time(&mut self) -> Result<i64, Box<dyn Error + Sync + Send>> {
+        let hour = self.number(2, 23)?;
+        if !self.eat(b':') {
+            return Err(self.error("expected \":\""));
+        }
+        let minute = self.number(2, 59)?;
+        if !self.eat(b':') {
+            return Err(self.error("expected \":\""));
+        }
+        let second = self.number(2, 59)?;
+
+        let mut usecs = 0;
+        if self.eat(b'.') {
+            let fraction = self.digits(1, 6)?;
+            usecs = fraction.parse::<i64>()? * 10_i64.pow(6 - fraction.len() as u32);
+        }
+
+        Ok(
+            (i64::from(hour) * 3600 + i64::from(minute) * 60 + i64::from(second)) * USECS_PER_SEC
+                + usecs,
+        )
+    }
+
+    // Returns the UTC offset in seconds.
+    fn offset(&mut self) -> Result<i64, Box<dyn Error + Sync + Send>> {
+        let sign = match self.bump() {
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Err(self.error("expected a UTC offset")),
+        };
+        let hours = self.number(2, 15)?;
+        let mut minutes = 0;
+        let mut seconds = 0;
+        if self.eat(b':') {
+            minutes = self.number(2, 59)?;
+            if self.eat(b':') {
+                seconds = self.number(2, 59)?;
+            }
+        }
+        Ok(sign * (i64::from(hours) * 3600 + i64::from(minutes) * 60 + i64::from(seconds)))
+    }
+}
+
+fn is_space(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0b' | '\x0c')
+}
+
+fn is_array_space(b: u8) -> bool {
+    is_space(char::from(b))
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Returns the number of days between 1970-01-01 and a date in the proleptic Gregorian calendar, using an astronomical
+// year numbering.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
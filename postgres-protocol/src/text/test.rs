@@ -0,0 +1,239 @@
+use super::*;
+
+// Inputs are taken from the output of a Postgres server.
+
+fn strings(elements: &[Option<&str>]) -> Vec<Option<String>> {
+    elements.iter().map(|e| e.map(str::to_string)).collect()
+}
+
+#[test]
+fn bool() {
+    for s in ["t", "true", "TRUE", " yes ", "y", "on", "1"] {
+        assert!(bool_from_text(s).unwrap(), "{:?}", s);
+    }
+    for s in ["f", "False", "no", "n", "of", "off", "0"] {
+        assert!(!bool_from_text(s).unwrap(), "{:?}", s);
+    }
+    for s in ["", "o", "tru e", "yess", "2", "10"] {
+        assert!(bool_from_text(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn bytea() {
+    let expected = [0x00, 0xff, 0x5c, 0x27];
+    assert_eq!(bytea_from_text("\\x00ff5c27").unwrap(), expected);
+    assert_eq!(bytea_from_text("\\x00 FF\n5c 27").unwrap(), expected);
+    assert_eq!(bytea_from_text("\\000\\377\\\\'").unwrap(), expected);
+    assert_eq!(bytea_from_text("\\x").unwrap(), []);
+    assert_eq!(bytea_from_text("").unwrap(), []);
+
+    for s in ["\\x0", "\\x0 0", "\\xzz", "\\400", "\\01", "a\\b", "\\"] {
+        assert!(bytea_from_text(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn array() {
+    let array = array_from_text(
+        r#"{"a,b","q\"x","back\\slash","",NULL," sp ","NULL","{}"}"#,
+        b',',
+    )
+    .unwrap();
+    assert_eq!(
+        array.dimensions(),
+        [ArrayDimension {
+            len: 8,
+            lower_bound: 1,
+        }]
+    );
+    assert_eq!(
+        array.into_elements(),
+        strings(&[
+            Some("a,b"),
+            Some("q\"x"),
+            Some("back\\slash"),
+            Some(""),
+            None,
+            Some(" sp "),
+            Some("NULL"),
+            Some("{}"),
+        ])
+    );
+
+    let array = array_from_text("[0:1][2:3]={{1,2},{3,4}}", b',').unwrap();
+    assert_eq!(
+        array.dimensions(),
+        [
+            ArrayDimension {
+                len: 2,
+                lower_bound: 0,
+            },
+            ArrayDimension {
+                len: 2,
+                lower_bound: 2,
+            },
+        ]
+    );
+    assert_eq!(
+        array.elements(),
+        strings(&[Some("1"), Some("2"), Some("3"), Some("4")])
+    );
+
+    let array = array_from_text(r#"{"a\"b", c\,d ,  e  , null}"#, b',').unwrap();
+    assert_eq!(
+        array.elements(),
+        strings(&[Some("a\"b"), Some("c,d"), Some("e"), None])
+    );
+
+    let array = array_from_text(" { 1 , 2 } ", b',').unwrap();
+    assert_eq!(array.elements(), strings(&[Some("1"), Some("2")]));
+
+    let array = array_from_text("{(1,2),(0,0);(3,4),(1,1)}", b';').unwrap();
+    assert_eq!(
+        array.elements(),
+        strings(&[Some("(1,2),(0,0)"), Some("(3,4),(1,1)")])
+    );
+
+    let array = array_from_text("{}", b',').unwrap();
+    assert_eq!(array.dimensions(), []);
+    assert_eq!(array.elements(), []);
+}
+
+#[test]
+fn array_errors() {
+    for s in [
+        "",
+        "1,2",
+        "{1,2",
+        "{1,,2}",
+        "{1,2}x",
+        "{{1,2},{3}}",
+        "{{1},2}",
+        "{1,{2}}",
+        "{{}}",
+        "{a\"b\"}",
+        "[1:3]={1,2}",
+        "[1:2]{1,2}",
+        "[1:2][1:2]={1,2}",
+        "{{{{{{{1}}}}}}}",
+    ] {
+        assert!(array_from_text(s, b',').is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn composite() {
+    let fields = composite_from_text(r#"("a,b","q""x","back\\slash","",," sp ","(x)")"#).unwrap();
+    assert_eq!(
+        fields,
+        strings(&[
+            Some("a,b"),
+            Some("q\"x"),
+            Some("back\\slash"),
+            Some(""),
+            None,
+            Some(" sp "),
+            Some("(x)"),
+        ])
+    );
+
+    assert_eq!(
+        composite_from_text(" (1, a b ,) ").unwrap(),
+        strings(&[Some("1"), Some(" a b "), None])
+    );
+    assert_eq!(composite_from_text("()").unwrap(), strings(&[None]));
+
+    for s in ["", "1,2", "(1,2", "(\"1)", "(1)x"] {
+        assert!(composite_from_text(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn date() {
+    assert_eq!(date_from_text("2000-01-01").unwrap(), 0);
+    assert_eq!(date_from_text("2024-02-29").unwrap(), 8825);
+    assert_eq!(date_from_text("1999-12-31").unwrap(), -1);
+    assert_eq!(date_from_text("0044-03-15 BC").unwrap(), -746117);
+    assert_eq!(date_from_text("0001-01-01 BC").unwrap(), -730485);
+    assert_eq!(date_from_text("0001-02-29 BC").unwrap(), -730426);
+    assert_eq!(date_from_text("0001-12-31 BC").unwrap(), -730120);
+    assert_eq!(date_from_text("infinity").unwrap(), i32::MAX);
+    assert_eq!(date_from_text("-infinity").unwrap(), i32::MIN);
+
+    for s in [
+        "2023-02-29",
+        "2000-13-01",
+        "2000-00-01",
+        "0000-01-01",
+        "0000-01-01 BC",
+        "0002-02-29 BC",
+        "2000-1-1",
+        "2000-01-01 AD",
+        "2000-01-01 00:00:00",
+    ] {
+        assert!(date_from_text(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn timestamp() {
+    assert_eq!(timestamp_from_text("2000-01-01 00:00:00").unwrap(), 0);
+    assert_eq!(
+        timestamp_from_text("2024-02-29 12:34:56.789012").unwrap(),
+        762525296789012
+    );
+    assert_eq!(
+        timestamp_from_text("2024-02-29T12:34:56.7").unwrap(),
+        762525296700000
+    );
+    assert_eq!(
+        timestamp_from_text("0044-03-15 00:00:00 BC").unwrap(),
+        -746117 * 86_400_000_000
+    );
+    assert_eq!(
+        timestamp_from_text("0001-12-31 23:59:59 BC").unwrap(),
+        -63082281601000000
+    );
+    assert_eq!(timestamp_from_text("infinity").unwrap(), i64::MAX);
+    assert_eq!(timestamp_from_text("-infinity").unwrap(), i64::MIN);
+
+    for s in [
+        "2000-01-01",
+        "2000-01-01 24:00:00",
+        "2000-01-01 00:60:00",
+        "2000-01-01 00:00:00.1234567",
+        "2000-01-01 00:00:00+00",
+        "300000-01-01 00:00:00",
+    ] {
+        assert!(timestamp_from_text(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn timestamptz() {
+    for s in [
+        "2000-01-01 00:00:00+00",
+        "2000-01-01 05:30:00+05:30",
+        "1999-12-31 20:30:00-03:30",
+    ] {
+        assert_eq!(timestamptz_from_text(s).unwrap(), 0, "{:?}", s);
+    }
+    assert_eq!(
+        timestamptz_from_text("1900-01-01 05:21:10+05:21:10").unwrap(),
+        -36524 * 86_400_000_000
+    );
+    assert_eq!(
+        timestamptz_from_text("0001-01-01 00:00:00+00 BC").unwrap(),
+        -730485 * 86_400_000_000
+    );
+    assert_eq!(timestamptz_from_text("infinity").unwrap(), i64::MAX);
+
+    for s in [
+        "2000-01-01 00:00:00",
+        "2000-01-01 00:00:00+5",
+        "2000-01-01 00:00:00+05:3",
+    ] {
+        assert!(timestamptz_from_text(s).is_err(), "{:?}", s);
+    }
+}
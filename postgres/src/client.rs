@@ -5,9 +5,11 @@ use crate::{
 };
 use std::task::Poll;
 use std::time::Duration;
+use tokio_postgres::snapshot::ConnectionSnapshot;
 use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
+use tokio_postgres::transform::{TransformTarget, TransformedParam};
 use tokio_postgres::types::{BorrowToSql, ToSql, Type};
-use tokio_postgres::{Error, Row, SimpleQueryMessage, Socket};
+use tokio_postgres::{Error, FromRow, Row, SimpleQueryMessage, Socket, TransactionOptions};
 
 /// A synchronous PostgreSQL client.
 pub struct Client {
@@ -83,6 +85,63 @@ impl Client {
         self.connection.block_on(self.client.execute(query, params))
     }
 
+    /// Executes a statement, decoding each resulting row directly into a `R`.
+    ///
+    /// This is intended for statements like `INSERT ... RETURNING id` which produce a batch of rows that are
+    /// immediately converted into values, and avoids building an intermediate `Vec<Row>`.
+    ///
+    /// The `query` argument can either be a `Statement`, or a raw query string. If the same statement will be
+    /// repeatedly executed (perhaps with different query parameters), consider preparing the statement up front
+    /// with the `prepare` method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use postgres::{Client, NoTls};
+    ///
+    /// # fn main() -> Result<(), postgres::Error> {
+    /// let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+    ///
+    /// let names = vec!["a", "b", "c"];
+    /// let ids: Vec<(i32,)> = client.execute_returning(
+    ///     "INSERT INTO foo (name) SELECT unnest($1::TEXT[]) RETURNING id",
+    ///     &[&names],
+    /// )?;
+    ///
+    /// println!("inserted {} rows", ids.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_returning<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.connection
+            .block_on(self.client.execute_returning(query, params))
+    }
+
+    /// Like `execute_returning`, but pre-allocates space for at least `capacity` rows.
+    pub fn execute_returning_with_capacity<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.connection.block_on(
+            self.client
+                .execute_returning_with_capacity(query, params, capacity),
+        )
+    }
+
     /// Executes a statement, returning the resulting rows.
     ///
     /// A statement may contain parameters, specified by `$n`, where `n` is the index of the parameter of the list
@@ -539,6 +598,51 @@ impl Client {
         TransactionBuilder::new(self.connection.as_ref(), self.client.build_transaction())
     }
 
+    /// Runs a closure inside of a new transaction with the specified options.
+    ///
+    /// Settings not specified by `options` fall back to the client's defaults. The transaction is committed if the
+    /// closure returns `Ok`, and rolled back otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use postgres::{Client, IsolationLevel, NoTls, TransactionOptions};
+    ///
+    /// # fn main() -> Result<(), postgres::Error> {
+    /// let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+    ///
+    /// let options = TransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+    /// client.transaction_with_options(options, |transaction| {
+    ///     transaction.execute("UPDATE foo SET bar = 10", &[])?;
+    ///     transaction.execute("UPDATE foo SET baz = 20", &[])?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction_with_options<F, T, E>(
+        &mut self,
+        options: TransactionOptions,
+        f: F,
+    ) -> Result<T, E>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> Result<T, E>,
+        E: From<Error>,
+    {
+        let mut transaction = self.build_transaction().options(options).start()?;
+        match f(&mut transaction) {
+            Ok(value) => {
+                transaction.commit()?;
+                Ok(value)
+            }
+            Err(e) => {
+                // the original error is more useful than any failure to roll back
+                let _ = transaction.rollback();
+                Err(e)
+            }
+        }
+    }
+
     /// Returns a structure providing access to asynchronous notifications.
     ///
     /// Use the `LISTEN` command to register this connection for notifications.
@@ -546,6 +650,29 @@ impl Client {
         Notifications::new(self.connection.as_ref())
     }
 
+    /// Wraps a query parameter so that it is transformed by the [`ValueTransform`] registered for `target`.
+    ///
+    /// The parameter is sent unchanged if no transform is registered for the target.
+    ///
+    /// [`ValueTransform`]: crate::transform::ValueTransform
+    pub fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.client.transform_param(target, value)
+    }
+
+    /// Returns a snapshot of the state of the connection, for diagnostic purposes.
+    ///
+    /// See the [`snapshot`](crate::snapshot) module for details.
+    pub fn debug_snapshot(&mut self) -> Result<ConnectionSnapshot, Error> {
+        self.connection.block_on(self.client.debug_snapshot())
+    }
+
     /// Constructs a cancellation token that can later be used to request cancellation of a query running on this
     /// connection.
     ///
@@ -12,11 +12,12 @@ use std::time::Duration;
 use tokio::runtime;
 #[doc(inline)]
 pub use tokio_postgres::config::{
-    ChannelBinding, Host, LoadBalanceHosts, SslMode, TargetSessionAttrs,
+    ChannelBinding, Host, LoadBalanceHosts, SqlRedaction, SslMode, TargetSessionAttrs,
 };
 use tokio_postgres::error::DbError;
 use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
-use tokio_postgres::{Error, Socket};
+use tokio_postgres::transform::{TransformTarget, ValueTransform};
+use tokio_postgres::{Error, Socket, TransactionOptions};
 
 /// Connection configuration.
 ///
@@ -448,6 +449,48 @@ impl Config {
         self.config.get_pgbouncer_mode()
     }
 
+    /// Sets the default options for transactions started by the client.
+    ///
+    /// These apply to `Client::transaction`, `Client::build_transaction`, and `Client::transaction_with_options`.
+    /// Settings specified explicitly when starting a transaction take precedence.
+    ///
+    /// Defaults to leaving every setting to the server.
+    pub fn transaction_options(&mut self, options: TransactionOptions) -> &mut Config {
+        self.config.transaction_options(options);
+        self
+    }
+
+    /// Gets the default transaction options.
+    pub fn get_transaction_options(&self) -> TransactionOptions {
+        self.config.get_transaction_options()
+    }
+
+    /// Sets how the text of SQL statements is included in error messages.
+    ///
+    /// Defaults to `SqlRedaction::Literals`.
+    pub fn sql_redaction(&mut self, sql_redaction: SqlRedaction) -> &mut Config {
+        self.config.sql_redaction(sql_redaction);
+        self
+    }
+
+    /// Gets the SQL redaction policy.
+    pub fn get_sql_redaction(&self) -> SqlRedaction {
+        self.config.get_sql_redaction()
+    }
+
+    /// Registers a transform for the values of the specified columns.
+    ///
+    /// Registering a transform for a target which already has one replaces it. Transforms apply to statements prepared
+    /// by clients created from this configuration. See the [`transform`](crate::transform) module for details.
+    pub fn value_transform(
+        &mut self,
+        target: TransformTarget,
+        transform: Arc<dyn ValueTransform>,
+    ) -> &mut Config {
+        self.config.value_transform(target, transform);
+        self
+    }
+
     /// Opens a connection to a PostgreSQL database.
     pub fn connect<T>(&self, tls: T) -> Result<Client, Error>
     where
@@ -1,7 +1,8 @@
+use crate::transform::{TransformTarget, TransformedParam};
 use crate::types::{BorrowToSql, ToSql, Type};
 use crate::{
-    Client, CopyInWriter, CopyOutReader, Error, Row, RowIter, SimpleQueryMessage, Statement,
-    ToStatement, Transaction,
+    Client, CopyInWriter, CopyOutReader, Error, FromRow, Row, RowIter, SimpleQueryMessage,
+    Statement, ToStatement, Transaction,
 };
 
 mod private {
@@ -17,6 +18,27 @@ pub trait GenericClient: private::Sealed {
     where
         T: ?Sized + ToStatement;
 
+    /// Like `Client::execute_returning`.
+    fn execute_returning<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement;
+
+    /// Like `Client::execute_returning_with_capacity`.
+    fn execute_returning_with_capacity<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement;
+
     /// Like `Client::query`.
     fn query<T>(&mut self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
@@ -81,6 +103,15 @@ pub trait GenericClient: private::Sealed {
 
     /// Like `Client::transaction`.
     fn transaction(&mut self) -> Result<Transaction<'_>, Error>;
+
+    /// Like `Client::transform_param`.
+    fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>;
 }
 
 impl private::Sealed for Client {}
@@ -93,6 +124,31 @@ impl GenericClient for Client {
         self.execute(query, params)
     }
 
+    fn execute_returning<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.execute_returning(query, params)
+    }
+
+    fn execute_returning_with_capacity<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.execute_returning_with_capacity(query, params, capacity)
+    }
+
     fn query<T>(&mut self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
         T: ?Sized + ToStatement,
@@ -177,6 +233,17 @@ impl GenericClient for Client {
     fn transaction(&mut self) -> Result<Transaction<'_>, Error> {
         self.transaction()
     }
+
+    fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.transform_param(target, value)
+    }
 }
 
 impl private::Sealed for Transaction<'_> {}
@@ -189,6 +256,31 @@ impl GenericClient for Transaction<'_> {
         self.execute(query, params)
     }
 
+    fn execute_returning<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.execute_returning(query, params)
+    }
+
+    fn execute_returning_with_capacity<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.execute_returning_with_capacity(query, params, capacity)
+    }
+
     fn query<T>(&mut self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
         T: ?Sized + ToStatement,
@@ -273,4 +365,15 @@ impl GenericClient for Transaction<'_> {
     fn transaction(&mut self) -> Result<Transaction<'_>, Error> {
         self.transaction()
     }
+
+    fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.transform_param(target, value)
+    }
 }
@@ -59,6 +59,7 @@
 //! | `with-eui48-1` | Enable support for the 1.0 version of the `eui48` crate. | [eui48](https://crates.io/crates/eui48) 1.0 | no |
 //! | `with-geo-types-0_6` | Enable support for the 0.6 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.6.0) 0.6 | no |
 //! | `with-geo-types-0_7` | Enable support for the 0.7 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.7.0) 0.7 | no |
+//! | `with-serde-1` | Implement `Serialize` for connection snapshots. | [serde](https://crates.io/crates/serde) 1.0 | no |
 //! | `with-serde_json-1` | Enable support for the `serde_json` crate. | [serde_json](https://crates.io/crates/serde_json) 1.0 | no |
 //! | `with-uuid-0_8` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 0.8 | no |
 //! | `with-uuid-1` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 1.0 | no |
@@ -68,8 +69,8 @@
 
 pub use fallible_iterator;
 pub use tokio_postgres::{
-    error, row, tls, types, Column, IsolationLevel, Notification, Portal, SimpleQueryMessage,
-    Socket, Statement, ToStatement,
+    error, row, snapshot, tls, transform, types, Column, IsolationLevel, Notification, Portal,
+    SimpleQueryMessage, Socket, Statement, ToStatement, TransactionOptions,
 };
 
 pub use crate::cancel_token::CancelToken;
@@ -83,7 +84,7 @@ pub use crate::generic_client::GenericClient;
 #[doc(inline)]
 pub use crate::notifications::Notifications;
 #[doc(no_inline)]
-pub use crate::row::{Row, SimpleQueryRow};
+pub use crate::row::{FromRow, Row, SimpleQueryRow};
 pub use crate::row_iter::RowIter;
 #[doc(no_inline)]
 pub use crate::tls::NoTls;
@@ -41,6 +41,24 @@ fn query_unprepared() {
     assert_eq!(rows[0].get::<_, &str>(0), "hello");
 }
 
+#[test]
+fn execute_returning() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query("CREATE TEMPORARY TABLE foo (id SERIAL PRIMARY KEY, name TEXT)")
+        .unwrap();
+
+    let names = vec!["alice", "bob"];
+    let ids: Vec<(i32,)> = client
+        .execute_returning(
+            "INSERT INTO foo (name) SELECT unnest($1::TEXT[]) RETURNING id",
+            &[&names],
+        )
+        .unwrap();
+    assert_eq!(ids, [(1,), (2,)]);
+}
+
 #[test]
 fn transaction_commit() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -127,6 +145,116 @@ fn transaction_drop_immediate_rollback() {
     assert_eq!(rows.len(), 1);
 }
 
+#[test]
+fn transaction_with_options() {
+    let mut client = Config::from_str("host=localhost port=5433 user=postgres")
+        .unwrap()
+        .transaction_options(TransactionOptions::new().read_only(true))
+        .connect(NoTls)
+        .unwrap();
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT)")
+        .unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+    let row = transaction
+        .query_one("SHOW transaction_read_only", &[])
+        .unwrap();
+    assert_eq!(row.get::<_, &str>(0), "on");
+    transaction.rollback().unwrap();
+
+    let options = TransactionOptions::new()
+        .isolation_level(IsolationLevel::Serializable)
+        .read_only(false);
+    client
+        .transaction_with_options(options, |transaction| {
+            transaction.execute("INSERT INTO foo (id) VALUES (1)", &[])
+        })
+        .unwrap();
+
+    let err = client
+        .transaction_with_options(options, |transaction| {
+            transaction.execute("INSERT INTO foo (id) VALUES (2)", &[])?;
+            transaction.execute("SELECT 1 / 0", &[])
+        })
+        .unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::DIVISION_BY_ZERO));
+
+    let rows = client.query("SELECT id FROM foo", &[]).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+}
+
+#[derive(Debug)]
+struct ReverseTransform;
+
+impl transform::ValueTransform for ReverseTransform {
+    fn decode(
+        &self,
+        _: &Column,
+        raw: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(raw.iter().rev().copied().collect())
+    }
+
+    fn encode(
+        &self,
+        _: &Type,
+        raw: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(raw.iter().rev().copied().collect())
+    }
+}
+
+#[test]
+fn transform_param_in_transaction() {
+    let mut client = Config::from_str("host=localhost port=5433 user=postgres")
+        .unwrap()
+        .value_transform("name".into(), std::sync::Arc::new(ReverseTransform))
+        .connect(NoTls)
+        .unwrap();
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let mut transaction = client.transaction().unwrap();
+    let name = transaction.transform_param("name", &"steven");
+    transaction
+        .execute("INSERT INTO foo (id, name) VALUES (1, $1)", &[&name])
+        .unwrap();
+    transaction.commit().unwrap();
+
+    client
+        .transaction_with_options(TransactionOptions::new(), |transaction| {
+            let name = GenericClient::transform_param(transaction, "name", &"joe");
+            transaction.execute("INSERT INTO foo (id, name) VALUES (2, $1)", &[&name])
+        })
+        .unwrap();
+
+    let rows = client
+        .query("SELECT name, name::BYTEA AS raw FROM foo ORDER BY id", &[])
+        .unwrap();
+    assert_eq!(rows[0].get::<_, &str>("name"), "steven");
+    assert_eq!(rows[0].get::<_, &[u8]>("raw"), b"nevets");
+    assert_eq!(rows[1].get::<_, &str>("name"), "joe");
+}
+
+#[test]
+fn debug_snapshot() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let snapshot = client.debug_snapshot().unwrap();
+    assert_eq!(snapshot.state(), snapshot::ConnectionState::Active);
+    assert!(snapshot.requests().is_empty());
+
+    // the end of the response to the query may not have been read yet
+    client.execute("SELECT 1", &[]).unwrap();
+    let snapshot = client.debug_snapshot().unwrap();
+    assert!(snapshot.requests().len() <= 1);
+}
+
 #[test]
 fn nested_transactions() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
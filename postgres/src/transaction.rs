@@ -1,7 +1,8 @@
 use crate::connection::ConnectionRef;
 use crate::{CancelToken, CopyInWriter, CopyOutReader, Portal, RowIter, Statement, ToStatement};
+use tokio_postgres::transform::{TransformTarget, TransformedParam};
 use tokio_postgres::types::{BorrowToSql, ToSql, Type};
-use tokio_postgres::{Error, Row, SimpleQueryMessage};
+use tokio_postgres::{Error, FromRow, Row, SimpleQueryMessage};
 
 /// A representation of a PostgreSQL database transaction.
 ///
@@ -70,6 +71,43 @@ impl<'a> Transaction<'a> {
             .block_on(self.transaction.as_ref().unwrap().execute(query, params))
     }
 
+    /// Like `Client::execute_returning`.
+    pub fn execute_returning<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .execute_returning(query, params),
+        )
+    }
+
+    /// Like `Client::execute_returning_with_capacity`.
+    pub fn execute_returning_with_capacity<R, T>(
+        &mut self,
+        query: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.connection.block_on(
+            self.transaction
+                .as_ref()
+                .unwrap()
+                .execute_returning_with_capacity(query, params, capacity),
+        )
+    }
+
     /// Like `Client::query`.
     pub fn query<T>(&mut self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
@@ -224,6 +262,21 @@ impl<'a> Transaction<'a> {
             .block_on(self.transaction.as_ref().unwrap().batch_execute(query))
     }
 
+    /// Like `Client::transform_param`.
+    pub fn transform_param<'b, T>(
+        &self,
+        target: T,
+        value: &'b (dyn ToSql + Sync),
+    ) -> TransformedParam<'b>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.transaction
+            .as_ref()
+            .unwrap()
+            .transform_param(target, value)
+    }
+
     /// Like `Client::cancel_token`.
     pub fn cancel_token(&self) -> CancelToken {
         CancelToken::new(self.transaction.as_ref().unwrap().cancel_token())
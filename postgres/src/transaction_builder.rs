@@ -1,5 +1,5 @@
 use crate::connection::ConnectionRef;
-use crate::{Error, IsolationLevel, Transaction};
+use crate::{Error, IsolationLevel, Transaction, TransactionOptions};
 
 /// A builder for database transactions.
 pub struct TransactionBuilder<'a> {
@@ -18,6 +18,14 @@ impl<'a> TransactionBuilder<'a> {
         }
     }
 
+    /// Applies a set of options to the transaction.
+    ///
+    /// Settings specified by `options` override those already set on the builder; the rest are left unchanged.
+    pub fn options(mut self, options: TransactionOptions) -> Self {
+        self.builder = self.builder.options(options);
+        self
+    }
+
     /// Sets the isolation level of the transaction.
     pub fn isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
         self.builder = self.builder.isolation_level(isolation_level);
@@ -1,24 +1,29 @@
 use crate::codec::BackendMessages;
-use crate::config::SslMode;
-use crate::connection::{Request, RequestMessages};
+use crate::config::{SqlRedaction, SslMode};
+use crate::connection::{Request, RequestMessages, SnapshotRequest};
 use crate::copy_out::CopyOutStream;
 #[cfg(feature = "runtime")]
 use crate::keepalive::KeepaliveConfig;
 use crate::query::RowStream;
 use crate::simple_query::SimpleQueryStream;
+use crate::snapshot::ConnectionSnapshot;
+use crate::statement::WeakStatement;
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 use crate::tls::TlsConnect;
+use crate::transform::{TransformTarget, TransformedParam, ValueTransforms};
 use crate::types::{Oid, ToSql, Type};
 #[cfg(feature = "runtime")]
 use crate::Socket;
 use crate::{
-    copy_in, copy_out, prepare, query, simple_query, slice_iter, CancelToken, CopyInSink, Error,
-    Row, SimpleQueryMessage, Statement, ToStatement, Transaction, TransactionBuilder,
+    copy_in, copy_out, prepare, query, simple_query, slice_iter, CancelToken, Config, CopyInSink,
+    Error, FromRow, Row, SimpleQueryMessage, Statement, ToStatement, Transaction,
+    TransactionBuilder, TransactionOptions,
 };
 use bytes::{Buf, BytesMut};
 use fallible_iterator::FallibleIterator;
-use futures_channel::mpsc;
+use futures_channel::{mpsc, oneshot};
+use futures_util::future::BoxFuture;
 use futures_util::{future, pin_mut, ready, StreamExt, TryStreamExt};
 use parking_lot::Mutex;
 use postgres_protocol::message::backend::Message;
@@ -59,6 +64,10 @@ impl Responses {
     pub async fn next(&mut self) -> Result<Message, Error> {
         future::poll_fn(|cx| self.poll_next(cx)).await
     }
+
+    pub fn buffered_data_rows(&self) -> usize {
+        self.cur.buffered_data_rows()
+    }
 }
 
 /// A cache of type info and prepared statements for fetching type info
@@ -81,11 +90,26 @@ struct CachedTypeInfo {
     types: HashMap<Oid, Type>,
 }
 
+/// Statements shared between handles of a client, keyed by query and then by parameter types.
+type SharedStatements = HashMap<String, HashMap<Vec<Type>, WeakStatement>>;
+
 pub struct InnerClient {
     sender: mpsc::UnboundedSender<Request>,
+    snapshot_sender: mpsc::UnboundedSender<SnapshotRequest>,
     pgbouncer_mode: bool,
     cached_typeinfo: Mutex<CachedTypeInfo>,
 
+    /// Statements prepared by any handle to this client, keyed by their query and parameter types, if statement
+    /// sharing is enabled.
+    ///
+    /// Only weak references are held, so a statement is still closed once the last handle to it is dropped.
+    shared_statements: Option<Mutex<SharedStatements>>,
+
+    /// The policy for including statement text in errors.
+    sql_redaction: SqlRedaction,
+
+    value_transforms: ValueTransforms,
+
     /// A buffer to use when writing out postgres commands.
     buffer: Mutex<BytesMut>,
 }
@@ -104,6 +128,14 @@ impl InnerClient {
         })
     }
 
+    pub fn sql_redaction(&self) -> SqlRedaction {
+        self.sql_redaction
+    }
+
+    pub fn value_transforms(&self) -> &ValueTransforms {
+        &self.value_transforms
+    }
+
     pub fn typeinfo(&self) -> Option<Statement> {
         if self.pgbouncer_mode {
             None
@@ -164,6 +196,37 @@ impl InnerClient {
         self.cached_typeinfo.lock().types.clear();
     }
 
+    pub fn shared_statement(&self, query: &str, types: &[Type]) -> Option<Statement> {
+        let statements = self.shared_statements.as_ref()?.lock();
+        statements.get(query)?.get(types)?.upgrade()
+    }
+
+    pub fn set_shared_statement(&self, query: &str, types: &[Type], statement: &Statement) {
+        if let Some(statements) = &self.shared_statements {
+            statements
+                .lock()
+                .entry(query.to_string())
+                .or_default()
+                .insert(types.to_vec(), statement.downgrade());
+        }
+    }
+
+    /// Removes closed statements for a query from the shared statement registry.
+    ///
+    /// Statements are registered under the parameter types they were prepared with rather than the ones the server
+    /// resolved, so every closed entry for the query is removed.
+    pub fn prune_shared_statements(&self, query: &str) {
+        if let Some(statements) = &self.shared_statements {
+            let mut statements = statements.lock();
+            if let Some(by_types) = statements.get_mut(query) {
+                by_types.retain(|_, s| !s.is_closed());
+                if by_types.is_empty() {
+                    statements.remove(query);
+                }
+            }
+        }
+    }
+
     /// Call the given function with a buffer to be used when writing out
     /// postgres commands.
     pub fn with_buf<F, R>(&self, f: F) -> R
@@ -200,6 +263,18 @@ pub(crate) enum Addr {
 ///
 /// The client is one half of what is returned when a connection is established. Users interact with the database
 /// through this client object.
+///
+/// Clients can be cloned. Clones are handles to the same connection, and their requests are pipelined over it in the
+/// order they are made. If [`Config::shared_statements`] is enabled, a statement prepared through one handle is reused
+/// by the others rather than being prepared again.
+///
+/// Since clones share a single session, they also share its transaction state. `transaction` only prevents the handle
+/// it is called on from being used while the transaction is open; requests made through other handles in the meantime
+/// run inside that transaction, and are committed or rolled back along with it. Avoid using other handles while a
+/// transaction is open, or give each task that needs transactions its own connection.
+///
+/// [`Config::shared_statements`]: crate::Config::shared_statements
+#[derive(Clone)]
 pub struct Client {
     inner: Arc<InnerClient>,
     #[cfg(feature = "runtime")]
@@ -207,28 +282,38 @@ pub struct Client {
     ssl_mode: SslMode,
     process_id: i32,
     secret_key: i32,
+    transaction_options: TransactionOptions,
 }
 
 impl Client {
     pub(crate) fn new(
         sender: mpsc::UnboundedSender<Request>,
-        ssl_mode: SslMode,
+        snapshot_sender: mpsc::UnboundedSender<SnapshotRequest>,
+        config: &Config,
         process_id: i32,
         secret_key: i32,
-        pgbouncer_mode: bool,
     ) -> Client {
         Client {
             inner: Arc::new(InnerClient {
                 sender,
-                pgbouncer_mode,
+                snapshot_sender,
+                pgbouncer_mode: config.pgbouncer_mode,
                 cached_typeinfo: Default::default(),
+                shared_statements: if config.shared_statements && !config.pgbouncer_mode {
+                    Some(Default::default())
+                } else {
+                    None
+                },
+                sql_redaction: config.sql_redaction,
+                value_transforms: config.value_transforms.clone(),
                 buffer: Default::default(),
             }),
             #[cfg(feature = "runtime")]
             socket_config: None,
-            ssl_mode,
+            ssl_mode: config.ssl_mode,
             process_id,
             secret_key,
+            transaction_options: config.transaction_options,
         }
     }
 
@@ -258,7 +343,14 @@ impl Client {
         query: &str,
         parameter_types: &[Type],
     ) -> Result<Statement, Error> {
-        prepare::prepare(&self.inner, query, parameter_types).await
+        if let Some(statement) = self.inner.shared_statement(query, parameter_types) {
+            return Ok(statement);
+        }
+
+        let statement = prepare::prepare(&self.inner, query, parameter_types).await?;
+        self.inner
+            .set_shared_statement(query, parameter_types, &statement);
+        Ok(statement)
     }
 
     /// Executes a statement, returning a vector of the resulting rows.
@@ -496,6 +588,66 @@ impl Client {
         query::execute(self.inner(), statement, params).await
     }
 
+    /// Executes a statement, decoding each resulting row directly into a `R`.
+    ///
+    /// This is intended for statements like `INSERT ... RETURNING id` which produce a batch of rows that are
+    /// immediately converted into values. Unlike `query`, no intermediate `Vec<Row>` is built.
+    ///
+    /// The output vector is sized up front from the number of rows which have already been received when the response
+    /// starts arriving. This is only a heuristic: it is exact for results small enough to arrive in a single read, but
+    /// larger results grow the vector as usual. Use `execute_returning_with_capacity` if the number of rows is known.
+    ///
+    /// A statement may contain parameters, specified by `$n`, where `n` is the index of the parameter of the list
+    /// provided, 1-indexed.
+    ///
+    /// The `statement` argument can either be a `Statement`, or a raw query string. If the same statement will be
+    /// repeatedly executed (perhaps with different query parameters), consider preparing the statement up front
+    /// with the `prepare` method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn async_main(client: &tokio_postgres::Client) -> Result<(), tokio_postgres::Error> {
+    /// let ids: Vec<(i32,)> = client
+    ///     .execute_returning(
+    ///         "INSERT INTO foo (bar) SELECT unnest($1::TEXT[]) RETURNING id",
+    ///         &[&vec!["a", "b", "c"]],
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_returning<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.execute_returning_with_capacity(statement, params, 0)
+            .await
+    }
+
+    /// Like `execute_returning`, but pre-allocates space for at least `capacity` rows.
+    ///
+    /// This is useful when the caller knows how many rows the statement will return, such as the number of values
+    /// passed to a multi-row `INSERT`.
+    pub async fn execute_returning_with_capacity<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        let statement = statement.__convert().into_statement(self).await?;
+        query::execute_returning(self.inner(), statement, slice_iter(params), capacity).await
+    }
+
     /// Executes a `COPY FROM STDIN` statement, returning a sink used to write the copy data.
     ///
     /// PostgreSQL does not support parameters in `COPY` statements, so this method does not take any. The copy *must*
@@ -526,7 +678,9 @@ impl Client {
     /// point. The simple query protocol returns the values in rows as strings rather than in their binary encodings,
     /// so the associated row type doesn't work with the `FromSql` trait. Rather than simply returning a list of the
     /// rows, this method returns a list of an enum which indicates either the completion of one of the commands,
-    /// or a row of data. This preserves the framing between the separate statements in the request.
+    /// or a row of data. This preserves the framing between the separate statements in the request. Values in these
+    /// rows are returned as the server sent them, without applying any
+    /// [`Config::value_transform`](crate::Config::value_transform) transforms.
     ///
     /// # Warning
     ///
@@ -570,6 +724,87 @@ impl Client {
         TransactionBuilder::new(self)
     }
 
+    /// Runs a closure inside of a new transaction with the specified options.
+    ///
+    /// Settings not specified by `options` fall back to the client's defaults. The transaction is committed if the
+    /// future returned by the closure resolves to `Ok`, and rolled back otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio_postgres::{Client, Error, IsolationLevel, TransactionOptions};
+    ///
+    /// # async fn f(client: &mut Client) -> Result<(), Error> {
+    /// let options = TransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+    ///
+    /// let count = client
+    ///     .transaction_with_options(options, |transaction| {
+    ///         Box::pin(async move {
+    ///             transaction.execute("UPDATE foo SET bar = 10", &[]).await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transaction_with_options<F, T, E>(
+        &mut self,
+        options: TransactionOptions,
+        f: F,
+    ) -> Result<T, E>
+    where
+        F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+        E: From<Error>,
+    {
+        let mut transaction = self.build_transaction().options(options).start().await?;
+        match f(&mut transaction).await {
+            Ok(value) => {
+                transaction.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                // the original error is more useful than any failure to roll back
+                let _ = transaction.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    pub(crate) fn transaction_options(&self) -> TransactionOptions {
+        self.transaction_options
+    }
+
+    /// Wraps a query parameter so that it is transformed by the [`ValueTransform`] registered for `target`.
+    ///
+    /// The parameter is sent unchanged if no transform is registered for the target.
+    ///
+    /// [`ValueTransform`]: crate::transform::ValueTransform
+    pub fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>,
+    {
+        let transform = self.inner.value_transforms().get(&target.into()).cloned();
+        TransformedParam::new(value, transform)
+    }
+
+    /// Returns a snapshot of the state of the connection associated with this client, for diagnostic purposes.
+    ///
+    /// The snapshot is taken by the connection, so this will only complete while the connection is being polled. A
+    /// snapshot which never arrives indicates that the connection's task has stalled, so callers should apply a
+    /// timeout. See the [`snapshot`](crate::snapshot) module for details.
+    pub async fn debug_snapshot(&self) -> Result<ConnectionSnapshot, Error> {
+        let (sender, receiver) = oneshot::channel();
+        self.inner
+            .snapshot_sender
+            .unbounded_send(sender)
+            .map_err(|_| Error::closed())?;
+        receiver.await.map_err(|_| Error::closed())
+    }
+
     /// Constructs a cancellation token that can later be used to request cancellation of a query running on the
     /// connection associated with this client.
     pub fn cancel_token(&self) -> CancelToken {
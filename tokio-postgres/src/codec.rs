@@ -24,6 +24,25 @@ impl BackendMessages {
     pub fn empty() -> BackendMessages {
         BackendMessages(BytesMut::new())
     }
+
+    /// Returns the number of `DataRow` messages buffered ahead of the next `CommandComplete`, without consuming them.
+    ///
+    /// If the `CommandComplete` is buffered as well this is the exact number of rows left in the response.
+    pub fn buffered_data_rows(&self) -> usize {
+        let mut idx = 0;
+        let mut rows = 0;
+
+        while let Ok(Some(header)) = backend::Header::parse(&self.0[idx..]) {
+            match header.tag() {
+                backend::DATA_ROW_TAG => rows += 1,
+                backend::COMMAND_COMPLETE_TAG => break,
+                _ => {}
+            }
+            idx += header.len() as usize + 1;
+        }
+
+        rows
+    }
 }
 
 impl FallibleIterator for BackendMessages {
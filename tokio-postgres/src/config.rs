@@ -8,9 +8,10 @@ use crate::keepalive::KeepaliveConfig;
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 use crate::tls::TlsConnect;
+use crate::transform::{TransformTarget, ValueTransform, ValueTransforms};
 #[cfg(feature = "runtime")]
 use crate::Socket;
-use crate::{Client, Connection, Error};
+use crate::{Client, Connection, Error, TransactionOptions};
 use std::borrow::Cow;
 #[cfg(unix)]
 use std::ffi::OsStr;
@@ -22,6 +23,7 @@ use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::str;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{error, fmt, iter, mem};
 use tokio::io::{AsyncRead, AsyncWrite};
@@ -72,6 +74,153 @@ pub enum LoadBalanceHosts {
     Random,
 }
 
+/// The policy for including the text of SQL statements in error messages.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SqlRedaction {
+    /// Include statements verbatim.
+    Disable,
+    /// Include statements with their string and numeric literals replaced by `?`.
+    Literals,
+    /// Do not include statements.
+    ///
+    /// Prepared statements will not retain the text of their queries.
+    Omit,
+}
+
+impl SqlRedaction {
+    /// Returns the text of `query` which this policy allows to be reported, if any.
+    pub(crate) fn apply(self, query: &str) -> Option<String> {
+        match self {
+            SqlRedaction::Disable => Some(query.to_string()),
+            SqlRedaction::Literals => Some(redact_literals(query)),
+            SqlRedaction::Omit => None,
+        }
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+// Only ASCII bytes are ever used as boundaries, so slicing `query` at them is always valid.
+fn redact_literals(query: &str) -> String {
+    let bytes = query.as_bytes();
+    let mut redacted = String::with_capacity(query.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let literal_end = match bytes[i] {
+            b'\'' => Some(skip_quoted(bytes, i, false)),
+            b'"' => {
+                i = skip_quoted_identifier(bytes, i);
+                None
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = bytes[i..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(bytes.len(), |p| i + p);
+                None
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = query[i + 2..].find("*/").map_or(bytes.len(), |p| i + p + 4);
+                None
+            }
+            b'$' => {
+                let tag_end = i
+                    + 1
+                    + bytes[i + 1..]
+                        .iter()
+                        .position(|&b| !is_ident_byte(b))
+                        .unwrap_or(bytes.len() - i - 1);
+                if bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+                    // a parameter reference like `$1`
+                    i = tag_end;
+                    None
+                } else if bytes.get(tag_end) == Some(&b'$') {
+                    let tag = &query[i..=tag_end];
+                    Some(
+                        query[tag_end + 1..]
+                            .find(tag)
+                            .map_or(bytes.len(), |p| tag_end + 1 + p + tag.len()),
+                    )
+                } else {
+                    i += 1;
+                    None
+                }
+            }
+            b'0'..=b'9' => Some(skip_number(bytes, i)),
+            b'.' if bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => Some(skip_number(bytes, i)),
+            b if is_ident_byte(b) => {
+                i += bytes[i..]
+                    .iter()
+                    .position(|&b| !is_ident_byte(b) && b != b'$')
+                    .unwrap_or(bytes.len() - i);
+                // `E'...'` strings allow backslash escapes
+                if bytes.get(i) == Some(&b'\'') && matches!(&bytes[start..i], b"E" | b"e") {
+                    Some(skip_quoted(bytes, i, true))
+                } else {
+                    None
+                }
+            }
+            _ => {
+                i += 1;
+                None
+            }
+        };
+
+        if let Some(end) = literal_end {
+            redacted.push_str(&query[copied..start]);
+            redacted.push('?');
+            copied = end;
+            i = end;
+        }
+    }
+
+    redacted.push_str(&query[copied..]);
+    redacted
+}
+
+fn skip_quoted(bytes: &[u8], start: usize, escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if escapes => i += 2,
+            b'\'' if bytes.get(i + 1) == Some(&b'\'') => i += 2,
+            b'\'' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn skip_quoted_identifier(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' if bytes.get(i + 1) == Some(&b'"') => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn skip_number(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'e' | b'E' if matches!(bytes.get(i + 1), Some(b'+' | b'-')) => i += 2,
+            b if b.is_ascii_alphanumeric() || b == b'_' || b == b'.' => i += 1,
+            _ => break,
+        }
+    }
+    i
+}
+
 /// A host specification.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Host {
@@ -210,6 +359,10 @@ pub struct Config {
     pub(crate) channel_binding: ChannelBinding,
     pub(crate) load_balance_hosts: LoadBalanceHosts,
     pub(crate) pgbouncer_mode: bool,
+    pub(crate) shared_statements: bool,
+    pub(crate) transaction_options: TransactionOptions,
+    pub(crate) sql_redaction: SqlRedaction,
+    pub(crate) value_transforms: ValueTransforms,
     pub(crate) search_path: Option<String>,
 }
 
@@ -245,6 +398,10 @@ impl Config {
             channel_binding: ChannelBinding::Prefer,
             load_balance_hosts: LoadBalanceHosts::Disable,
             pgbouncer_mode: false,
+            shared_statements: false,
+            transaction_options: TransactionOptions::new(),
+            sql_redaction: SqlRedaction::Literals,
+            value_transforms: ValueTransforms::default(),
             search_path: None,
         }
     }
@@ -543,6 +700,68 @@ impl Config {
         self.pgbouncer_mode
     }
 
+    /// When enabled, statements prepared by one handle of a cloned [`Client`] are shared with the other handles.
+    ///
+    /// Preparing a query which is already prepared with the same parameter types returns the existing statement
+    /// rather than preparing it again. Statements are closed once every handle to them has been dropped. This has no
+    /// effect in pgBouncer mode.
+    ///
+    /// Cloned handles share the connection's session, including any open transaction, so a statement executed through
+    /// one handle while another has a transaction open runs inside that transaction. See [`Client`] for details.
+    ///
+    /// Defaults to `false`.
+    pub fn shared_statements(&mut self, enable: bool) -> &mut Config {
+        self.shared_statements = enable;
+        self
+    }
+
+    /// Gets the shared statements status.
+    pub fn get_shared_statements(&self) -> bool {
+        self.shared_statements
+    }
+
+    /// Sets the default options for transactions started by the client.
+    ///
+    /// These apply to `Client::transaction`, `Client::build_transaction`, and `Client::transaction_with_options`.
+    /// Settings specified explicitly when starting a transaction take precedence.
+    ///
+    /// Defaults to leaving every setting to the server.
+    pub fn transaction_options(&mut self, options: TransactionOptions) -> &mut Config {
+        self.transaction_options = options;
+        self
+    }
+
+    /// Gets the default transaction options.
+    pub fn get_transaction_options(&self) -> TransactionOptions {
+        self.transaction_options
+    }
+
+    /// Sets how the text of SQL statements is included in error messages.
+    ///
+    /// Defaults to `SqlRedaction::Literals`.
+    pub fn sql_redaction(&mut self, sql_redaction: SqlRedaction) -> &mut Config {
+        self.sql_redaction = sql_redaction;
+        self
+    }
+
+    /// Gets the SQL redaction policy.
+    pub fn get_sql_redaction(&self) -> SqlRedaction {
+        self.sql_redaction
+    }
+
+    /// Registers a transform for the values of the specified columns.
+    ///
+    /// Registering a transform for a target which already has one replaces it. Transforms apply to statements prepared
+    /// by clients created from this configuration. See the [`transform`](crate::transform) module for details.
+    pub fn value_transform(
+        &mut self,
+        target: TransformTarget,
+        transform: Arc<dyn ValueTransform>,
+    ) -> &mut Config {
+        self.value_transforms.insert(target, transform);
+        self
+    }
+
     /// Sets the search_path.
     pub fn search_path(&mut self, search_path: String) -> &mut Config {
         self.search_path = Some(search_path);
@@ -1153,7 +1372,10 @@ impl<'a> UrlParser<'a> {
 mod tests {
     use std::net::IpAddr;
 
-    use crate::{config::Host, Config};
+    use crate::{
+        config::{Host, SqlRedaction},
+        Config,
+    };
 
     #[test]
     fn test_simple_parsing() {
@@ -1185,4 +1407,34 @@ mod tests {
         let s = "user=pass_user dbname=postgres host=host1 hostaddr=127.0.0 port=26257";
         s.parse::<Config>().err().unwrap();
     }
+
+    #[test]
+    fn test_redact_literals() {
+        let redact = |query| SqlRedaction::Literals.apply(query).unwrap();
+
+        assert_eq!(
+            redact("SELECT * FROM users WHERE name = 'bob' AND age > 30 AND id = $1"),
+            "SELECT * FROM users WHERE name = ? AND age > ? AND id = $1",
+        );
+        assert_eq!(
+            redact("SELECT 'it''s', E'\\'x', 1.5e-3, .5"),
+            "SELECT ?, ?, ?, ?"
+        );
+        assert_eq!(
+            redact("SELECT $$secret$$, $tag$a $$ b$tag$, col2, \"it's\" FROM t1"),
+            "SELECT ?, ?, col2, \"it's\" FROM t1",
+        );
+        assert_eq!(
+            redact("SELECT 1 -- it's\n/* 'x' */ FROM tab$1"),
+            "SELECT ? -- it's\n/* 'x' */ FROM tab$1",
+        );
+        assert_eq!(redact("SELECT 'unterminated"), "SELECT ?");
+        assert_eq!(redact("SELECT 'é', 'ü' || é1"), "SELECT ?, ? || é1");
+
+        assert_eq!(
+            SqlRedaction::Disable.apply("SELECT 'x'").as_deref(),
+            Some("SELECT 'x'")
+        );
+        assert_eq!(SqlRedaction::Omit.apply("SELECT 'x'"), None);
+    }
 }
@@ -107,14 +107,15 @@ where
     let (process_id, secret_key, parameters) = read_info(&mut stream).await?;
 
     let (sender, receiver) = mpsc::unbounded();
-    let client = Client::new(
-        sender,
-        config.ssl_mode,
-        process_id,
-        secret_key,
-        config.pgbouncer_mode,
+    let (snapshot_sender, snapshot_receiver) = mpsc::unbounded();
+    let client = Client::new(sender, snapshot_sender, config, process_id, secret_key);
+    let connection = Connection::new(
+        stream.inner,
+        stream.delayed,
+        parameters,
+        receiver,
+        snapshot_receiver,
     );
-    let connection = Connection::new(stream.inner, stream.delayed, parameters, receiver);
 
     Ok((client, connection))
 }
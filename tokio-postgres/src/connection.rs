@@ -2,10 +2,11 @@ use crate::codec::{BackendMessage, BackendMessages, FrontendMessage, PostgresCod
 use crate::copy_in::CopyInReceiver;
 use crate::error::DbError;
 use crate::maybe_tls_stream::MaybeTlsStream;
+use crate::snapshot::{ConnectionSnapshot, ConnectionState, RequestSnapshot, ResponseState};
 use crate::{AsyncMessage, Error, Notification};
 use bytes::BytesMut;
 use fallible_iterator::FallibleIterator;
-use futures_channel::mpsc;
+use futures_channel::{mpsc, oneshot};
 use futures_util::{ready, stream::FusedStream, Sink, Stream, StreamExt};
 use log::{info, trace};
 use postgres_protocol::message::backend::Message;
@@ -14,6 +15,7 @@ use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Instant;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_util::codec::Framed;
 
@@ -27,8 +29,13 @@ pub struct Request {
     pub sender: mpsc::Sender<BackendMessages>,
 }
 
+/// A request from a `Client` for a snapshot of the connection's state.
+pub type SnapshotRequest = oneshot::Sender<ConnectionSnapshot>;
+
 pub struct Response {
     sender: mpsc::Sender<BackendMessages>,
+    started: Option<Instant>,
+    batches_delivered: u64,
 }
 
 #[derive(PartialEq, Debug)]
@@ -50,6 +57,7 @@ pub struct Connection<S, T> {
     stream: Framed<MaybeTlsStream<S, T>, PostgresCodec>,
     parameters: HashMap<String, String>,
     receiver: mpsc::UnboundedReceiver<Request>,
+    snapshot_requests: mpsc::UnboundedReceiver<SnapshotRequest>,
     pending_request: Option<RequestMessages>,
     pending_responses: VecDeque<BackendMessage>,
     responses: VecDeque<Response>,
@@ -66,11 +74,13 @@ where
         pending_responses: VecDeque<BackendMessage>,
         parameters: HashMap<String, String>,
         receiver: mpsc::UnboundedReceiver<Request>,
+        snapshot_requests: mpsc::UnboundedReceiver<SnapshotRequest>,
     ) -> Connection<S, T> {
         Connection {
             stream,
             parameters,
             receiver,
+            snapshot_requests,
             pending_request: None,
             pending_responses,
             responses: VecDeque::new(),
@@ -146,6 +156,7 @@ where
             match response.sender.poll_ready(cx) {
                 Poll::Ready(Ok(())) => {
                     let _ = response.sender.start_send(messages);
+                    response.batches_delivered += 1;
                     if !request_complete {
                         self.responses.push_front(response);
                     }
@@ -184,6 +195,8 @@ where
                 trace!("polled new request");
                 self.responses.push_back(Response {
                     sender: request.sender,
+                    started: now(),
+                    batches_delivered: 0,
                 });
                 Poll::Ready(Some(request.messages))
             }
@@ -262,6 +275,14 @@ where
         }
     }
 
+    // Snapshot requests are answered separately from other requests so that they aren't held up by a stalled one.
+    fn poll_snapshot_requests(&mut self, cx: &mut Context<'_>) {
+        while let Poll::Ready(Some(sender)) = self.snapshot_requests.poll_next_unpin(cx) {
+            trace!("poll_snapshot_requests: taking snapshot");
+            let _ = sender.send(self.debug_snapshot());
+        }
+    }
+
     fn poll_flush(&mut self, cx: &mut Context<'_>) -> Result<(), Error> {
         match Pin::new(&mut self.stream)
             .poll_flush(cx)
@@ -298,6 +319,57 @@ where
         self.parameters.get(name).map(|s| &**s)
     }
 
+    /// Returns a snapshot of the state of the connection, for diagnostic purposes.
+    ///
+    /// Once the connection has been spawned onto an executor, use `Client::debug_snapshot` instead. See the
+    /// [`snapshot`](crate::snapshot) module for details.
+    pub fn debug_snapshot(&self) -> ConnectionSnapshot {
+        // messages are only held for normal responses while waiting for the client to consume earlier ones
+        let blocked = self
+            .pending_responses
+            .iter()
+            .any(|m| matches!(m, BackendMessage::Normal { .. }));
+        let now = now();
+
+        let requests = self
+            .responses
+            .iter()
+            .enumerate()
+            .map(|(i, response)| {
+                let state = if response.sender.is_closed() {
+                    ResponseState::Abandoned
+                } else if i == 0 && blocked {
+                    ResponseState::Blocked
+                } else if response.batches_delivered > 0 {
+                    ResponseState::Receiving
+                } else {
+                    ResponseState::Waiting
+                };
+
+                RequestSnapshot {
+                    age: now
+                        .zip(response.started)
+                        .map(|(now, started)| now.duration_since(started)),
+                    state,
+                    batches_delivered: response.batches_delivered,
+                }
+            })
+            .collect();
+
+        ConnectionSnapshot {
+            state: match self.state {
+                State::Active => ConnectionState::Active,
+                State::Terminating => ConnectionState::Terminating,
+                State::Closing => ConnectionState::Closing,
+            },
+            requests,
+            copy_in: matches!(self.pending_request, Some(RequestMessages::CopyIn(_))),
+            buffered_messages: self.pending_responses.len(),
+            read_buffer_len: self.stream.read_buffer().len(),
+            write_buffer_len: self.stream.write_buffer().len(),
+        }
+    }
+
     /// Polls for asynchronous messages from the server.
     ///
     /// The server can send notices as well as notifications asynchronously to the client. Applications that wish to
@@ -309,6 +381,7 @@ where
         &mut self,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<AsyncMessage, Error>>> {
+        self.poll_snapshot_requests(cx);
         let message = self.poll_read(cx)?;
         let want_flush = self.poll_write(cx)?;
         if want_flush {
@@ -325,6 +398,17 @@ where
     }
 }
 
+// `Instant::now` panics on wasm32-unknown-unknown.
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> Option<Instant> {
+    Some(Instant::now())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> Option<Instant> {
+    None
+}
+
 impl<S, T> Future for Connection<S, T>
 where
     S: AsyncRead + AsyncWrite + Unpin,
@@ -1,5 +1,7 @@
 //! Errors.
 
+use crate::types::Type;
+use crate::Statement;
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::{ErrorFields, ErrorResponseBody};
 use std::error::{self, Error as _Error};
@@ -345,7 +347,7 @@ enum Kind {
     ToSql(usize),
     FromSql(usize),
     Column(String),
-    Parameters(usize, usize),
+    Parameters(Box<ParameterMismatch>),
     Closed,
     Db,
     Parse,
@@ -359,6 +361,42 @@ enum Kind {
     Timeout,
 }
 
+#[derive(Debug, PartialEq)]
+struct ParameterMismatch {
+    real: usize,
+    statement: String,
+    params: Vec<Type>,
+    query: Option<String>,
+}
+
+impl fmt::Display for ParameterMismatch {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "expected {} parameters but got {}",
+            self.params.len(),
+            self.real
+        )?;
+        if self.statement.is_empty() {
+            fmt.write_str(" for unnamed statement")?;
+        } else {
+            write!(fmt, " for statement {}", self.statement)?;
+        }
+        fmt.write_str(" with parameter types [")?;
+        for (i, type_) in self.params.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(", ")?;
+            }
+            write!(fmt, "{}", type_)?;
+        }
+        fmt.write_str("]")?;
+        if let Some(query) = &self.query {
+            write!(fmt, ": {}", query)?;
+        }
+        Ok(())
+    }
+}
+
 struct ErrorInner {
     kind: Kind,
     cause: Option<Box<dyn error::Error + Sync + Send>>,
@@ -385,9 +423,7 @@ impl fmt::Display for Error {
             Kind::ToSql(idx) => write!(fmt, "error serializing parameter {}", idx)?,
             Kind::FromSql(idx) => write!(fmt, "error deserializing column {}", idx)?,
             Kind::Column(column) => write!(fmt, "invalid column `{}`", column)?,
-            Kind::Parameters(real, expected) => {
-                write!(fmt, "expected {expected} parameters but got {real}")?
-            }
+            Kind::Parameters(mismatch) => write!(fmt, "{}", mismatch)?,
             Kind::Closed => fmt.write_str("connection closed")?,
             Kind::Db => fmt.write_str("db error")?,
             Kind::Parse => fmt.write_str("error parsing response from server")?,
@@ -479,8 +515,14 @@ impl Error {
         Error::new(Kind::Column(column), None)
     }
 
-    pub(crate) fn parameters(real: usize, expected: usize) -> Error {
-        Error::new(Kind::Parameters(real, expected), None)
+    pub(crate) fn parameters(real: usize, statement: &Statement) -> Error {
+        let mismatch = ParameterMismatch {
+            real,
+            statement: statement.name().to_string(),
+            params: statement.params().to_vec(),
+            query: statement.redacted_query(),
+        };
+        Error::new(Kind::Parameters(Box::new(mismatch)), None)
     }
 
     pub(crate) fn tls(e: Box<dyn error::Error + Sync + Send>) -> Error {
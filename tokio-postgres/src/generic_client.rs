@@ -1,6 +1,7 @@
 use crate::query::RowStream;
+use crate::transform::{TransformTarget, TransformedParam};
 use crate::types::{BorrowToSql, ToSql, Type};
-use crate::{Client, Error, Row, SimpleQueryMessage, Statement, ToStatement, Transaction};
+use crate::{Client, Error, FromRow, Row, SimpleQueryMessage, Statement, ToStatement, Transaction};
 use async_trait::async_trait;
 
 mod private {
@@ -25,6 +26,27 @@ pub trait GenericClient: private::Sealed {
         I: IntoIterator<Item = P> + Sync + Send,
         I::IntoIter: ExactSizeIterator;
 
+    /// Like [`Client::execute_returning`].
+    async fn execute_returning<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow + Send,
+        T: ?Sized + ToStatement + Sync + Send;
+
+    /// Like [`Client::execute_returning_with_capacity`].
+    async fn execute_returning_with_capacity<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow + Send,
+        T: ?Sized + ToStatement + Sync + Send;
+
     /// Like [`Client::query`].
     async fn query<T>(&self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
@@ -88,6 +110,15 @@ pub trait GenericClient: private::Sealed {
     /// Like [`Client::simple_query`].
     async fn simple_query(&self, query: &str) -> Result<Vec<SimpleQueryMessage>, Error>;
 
+    /// Like [`Client::transform_param`].
+    fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>;
+
     /// Returns a reference to the underlying [`Client`].
     fn client(&self) -> &Client;
 }
@@ -113,6 +144,32 @@ impl GenericClient for Client {
         self.execute_raw(statement, params).await
     }
 
+    async fn execute_returning<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow + Send,
+        T: ?Sized + ToStatement + Sync + Send,
+    {
+        self.execute_returning(statement, params).await
+    }
+
+    async fn execute_returning_with_capacity<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow + Send,
+        T: ?Sized + ToStatement + Sync + Send,
+    {
+        self.execute_returning_with_capacity(statement, params, capacity)
+            .await
+    }
+
     async fn query<T>(&self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
         T: ?Sized + ToStatement + Sync + Send,
@@ -192,6 +249,17 @@ impl GenericClient for Client {
         self.simple_query(query).await
     }
 
+    fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.transform_param(target, value)
+    }
+
     fn client(&self) -> &Client {
         self
     }
@@ -219,6 +287,32 @@ impl GenericClient for Transaction<'_> {
         self.execute_raw(statement, params).await
     }
 
+    async fn execute_returning<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow + Send,
+        T: ?Sized + ToStatement + Sync + Send,
+    {
+        self.execute_returning(statement, params).await
+    }
+
+    async fn execute_returning_with_capacity<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow + Send,
+        T: ?Sized + ToStatement + Sync + Send,
+    {
+        self.execute_returning_with_capacity(statement, params, capacity)
+            .await
+    }
+
     async fn query<T>(&self, query: &T, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error>
     where
         T: ?Sized + ToStatement + Sync + Send,
@@ -299,6 +393,17 @@ impl GenericClient for Transaction<'_> {
         self.simple_query(query).await
     }
 
+    fn transform_param<'a, T>(
+        &self,
+        target: T,
+        value: &'a (dyn ToSql + Sync),
+    ) -> TransformedParam<'a>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.transform_param(target, value)
+    }
+
     fn client(&self) -> &Client {
         self.client()
     }
@@ -112,6 +112,7 @@
 //! | `with-geo-types-0_6` | Enable support for the 0.6 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.6.0) 0.6 | no |
 //! | `with-geo-types-0_7` | Enable support for the 0.7 version of the `geo-types` crate. | [geo-types](https://crates.io/crates/geo-types/0.7.0) 0.7 | no |
 //! | `with-jiff-0_1` | Enable support for the 0.1 version of the `jiff` crate. | [jiff](https://crates.io/crates/jiff/0.1.0) 0.1 | no |
+//! | `with-serde-1` | Implement `Serialize` for connection snapshots. | [serde](https://crates.io/crates/serde) 1.0 | no |
 //! | `with-serde_json-1` | Enable support for the `serde_json` crate. | [serde_json](https://crates.io/crates/serde_json) 1.0 | no |
 //! | `with-uuid-0_8` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 0.8 | no |
 //! | `with-uuid-1` | Enable support for the `uuid` crate. | [uuid](https://crates.io/crates/uuid) 1.0 | no |
@@ -130,7 +131,7 @@ pub use crate::error::Error;
 pub use crate::generic_client::GenericClient;
 pub use crate::portal::Portal;
 pub use crate::query::RowStream;
-pub use crate::row::{Row, SimpleQueryRow};
+pub use crate::row::{FromRow, Row, SimpleQueryRow};
 pub use crate::simple_query::{SimpleColumn, SimpleQueryStream};
 #[cfg(feature = "runtime")]
 pub use crate::socket::Socket;
@@ -140,7 +141,7 @@ use crate::tls::MakeTlsConnect;
 pub use crate::tls::NoTls;
 pub use crate::to_statement::ToStatement;
 pub use crate::transaction::Transaction;
-pub use crate::transaction_builder::{IsolationLevel, TransactionBuilder};
+pub use crate::transaction_builder::{IsolationLevel, TransactionBuilder, TransactionOptions};
 use crate::types::ToSql;
 use std::sync::Arc;
 
@@ -172,6 +173,7 @@ mod prepare;
 mod query;
 pub mod row;
 mod simple_query;
+pub mod snapshot;
 #[cfg(feature = "runtime")]
 mod socket;
 mod statement;
@@ -179,6 +181,7 @@ pub mod tls;
 mod to_statement;
 mod transaction;
 mod transaction_builder;
+pub mod transform;
 pub mod types;
 
 /// A convenience function which parses a connection string and connects to the database.
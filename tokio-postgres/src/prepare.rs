@@ -105,7 +105,7 @@ pub async fn prepare(
         }
     }
 
-    Ok(Statement::new(client, name, parameters, columns))
+    Ok(Statement::new(client, name, query, parameters, columns))
 }
 
 fn prepare_rec<'a>(
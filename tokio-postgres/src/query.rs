@@ -3,7 +3,7 @@ use crate::codec::FrontendMessage;
 use crate::connection::RequestMessages;
 use crate::prepare::get_type;
 use crate::types::{BorrowToSql, IsNull};
-use crate::{Column, Error, Portal, Row, Statement};
+use crate::{Column, Error, FromRow, Portal, Row, Statement};
 use bytes::{Bytes, BytesMut};
 use fallible_iterator::FallibleIterator;
 use futures_util::{ready, Stream};
@@ -12,11 +12,11 @@ use pin_project_lite::pin_project;
 use postgres_protocol::message::backend::{CommandCompleteBody, Message};
 use postgres_protocol::message::frontend;
 use postgres_types::Type;
-use std::fmt;
 use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::{cmp, fmt};
 
 struct BorrowToSqlParamsDebug<'a, T>(&'a [T]);
 
@@ -92,7 +92,7 @@ where
             Message::ParseComplete | Message::BindComplete | Message::ParameterDescription(_) => {}
             Message::NoData => {
                 return Ok(RowStream {
-                    statement: Statement::unnamed(vec![], vec![]),
+                    statement: Statement::unnamed(client, vec![], vec![]),
                     responses,
                     rows_affected: None,
                     _p: PhantomPinned,
@@ -112,7 +112,7 @@ where
                     columns.push(column);
                 }
                 return Ok(RowStream {
-                    statement: Statement::unnamed(vec![], columns),
+                    statement: Statement::unnamed(client, vec![], columns),
                     responses,
                     rows_affected: None,
                     _p: PhantomPinned,
@@ -194,6 +194,45 @@ where
     }
 }
 
+pub async fn execute_returning<R, P, I>(
+    client: &InnerClient,
+    statement: Statement,
+    params: I,
+    capacity: usize,
+) -> Result<Vec<R>, Error>
+where
+    R: FromRow,
+    P: BorrowToSql,
+    I: IntoIterator<Item = P>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let buf = if log_enabled!(Level::Debug) {
+        let params = params.into_iter().collect::<Vec<_>>();
+        debug!(
+            "executing statement {} with parameters: {:?}",
+            statement.name(),
+            BorrowToSqlParamsDebug(params.as_slice()),
+        );
+        encode(client, &statement, params)?
+    } else {
+        encode(client, &statement, params)?
+    };
+    let mut responses = start(client, buf).await?;
+
+    // Small results usually arrive in the same read as the BindComplete, in which case we know exactly how many rows
+    // to expect before decoding any of them. Otherwise this only counts the rows received so far, and the vector grows
+    // as the rest arrive.
+    let mut rows = Vec::with_capacity(cmp::max(capacity, responses.buffered_data_rows()));
+    loop {
+        match responses.next().await? {
+            Message::DataRow(body) => rows.push(R::from_row(Row::new(statement.clone(), body)?)?),
+            Message::CommandComplete(_) | Message::EmptyQueryResponse => {}
+            Message::ReadyForQuery(_) => return Ok(rows),
+            _ => return Err(Error::unexpected_message()),
+        }
+    }
+}
+
 async fn start(client: &InnerClient, buf: Bytes) -> Result<Responses, Error> {
     let mut responses = client.send(RequestMessages::Single(FrontendMessage::Raw(buf)))?;
 
@@ -232,7 +271,7 @@ where
 {
     let params = params.into_iter();
     if params.len() != statement.params().len() {
-        return Err(Error::parameters(params.len(), statement.params().len()));
+        return Err(Error::parameters(params.len(), statement));
     }
 
     encode_bind_raw(
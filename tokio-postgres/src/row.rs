@@ -5,6 +5,7 @@ use crate::simple_query::SimpleColumn;
 use crate::statement::Column;
 use crate::types::{FromSql, Type, WrongType};
 use crate::{Error, Statement};
+use bytes::{Bytes, BytesMut};
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::DataRowBody;
 use std::fmt;
@@ -98,7 +99,7 @@ where
 #[derive(Clone)]
 pub struct Row {
     statement: Statement,
-    body: DataRowBody,
+    body: Bytes,
     ranges: Vec<Option<Range<usize>>>,
 }
 
@@ -113,11 +114,41 @@ impl fmt::Debug for Row {
 impl Row {
     pub(crate) fn new(statement: Statement, body: DataRowBody) -> Result<Row, Error> {
         let ranges = body.ranges().collect().map_err(Error::parse)?;
-        Ok(Row {
+        let mut row = Row {
             statement,
-            body,
+            body: body.buffer_bytes().clone(),
             ranges,
-        })
+        };
+        if row.statement.has_transforms() {
+            row.transform()?;
+        }
+        Ok(row)
+    }
+
+    /// Replaces the values of columns with transforms by their transformed values.
+    fn transform(&mut self) -> Result<(), Error> {
+        let mut buf = BytesMut::with_capacity(self.body.len());
+        for (idx, range) in self.ranges.iter_mut().enumerate() {
+            let value = match range {
+                Some(range) => &self.body[range.clone()],
+                None => continue,
+            };
+
+            let start = buf.len();
+            match self.statement.column_transform(idx) {
+                Some(transform) => {
+                    let value = transform
+                        .transform
+                        .decode(&self.statement.columns()[idx], value)
+                        .map_err(|e| Error::from_sql(e, idx))?;
+                    buf.extend_from_slice(&value);
+                }
+                None => buf.extend_from_slice(value),
+            }
+            *range = Some(start..buf.len());
+        }
+        self.body = buf.freeze();
+        Ok(())
     }
 
     /// Returns information about the columns of data in the row.
@@ -173,7 +204,10 @@ impl Row {
             None => return Err(Error::column(idx.to_string())),
         };
 
-        let ty = self.columns()[idx].type_();
+        let ty = match self.statement.column_transform(idx) {
+            Some(transform) => &transform.type_,
+            None => self.columns()[idx].type_(),
+        };
         if !T::accepts(ty) {
             return Err(Error::from_sql(
                 Box::new(WrongType::new::<T>(ty.clone())),
@@ -187,10 +221,47 @@ impl Row {
     /// Get the raw bytes for the column at the given index.
     fn col_buffer(&self, idx: usize) -> Option<&[u8]> {
         let range = self.ranges[idx].to_owned()?;
-        Some(&self.body.buffer()[range])
+        Some(&self.body[range])
     }
 }
 
+/// A trait for types which can be created from a [`Row`].
+///
+/// It is implemented for `Row` itself, and for tuples of up to 8 [`FromSql`] types, which are decoded from the row's
+/// columns by position.
+pub trait FromRow: Sized {
+    /// Creates a value from a row.
+    fn from_row(row: Row) -> Result<Self, Error>;
+}
+
+impl FromRow for Row {
+    fn from_row(row: Row) -> Result<Row, Error> {
+        Ok(row)
+    }
+}
+
+macro_rules! from_row_tuple {
+    ($($t:ident $idx:tt),+) => {
+        impl<$($t),+> FromRow for ($($t,)+)
+        where
+            $($t: for<'a> FromSql<'a>,)+
+        {
+            fn from_row(row: Row) -> Result<Self, Error> {
+                Ok(($(row.try_get::<_, $t>($idx)?,)+))
+            }
+        }
+    };
+}
+
+from_row_tuple!(T0 0);
+from_row_tuple!(T0 0, T1 1);
+from_row_tuple!(T0 0, T1 1, T2 2);
+from_row_tuple!(T0 0, T1 1, T2 2, T3 3);
+from_row_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+from_row_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+from_row_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+from_row_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+
 impl AsName for SimpleColumn {
     fn as_name(&self) -> &str {
         self.name()
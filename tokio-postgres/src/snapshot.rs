@@ -0,0 +1,122 @@
+//! Diagnostic snapshots of the state of a connection.
+//!
+//! A [`ConnectionSnapshot`] describes the connection from the client's point of view: the requests it is waiting on
+//! responses to, how far along each response is, and how much data is buffered in each direction. This is useful when
+//! diagnosing queries which appear to hang, to determine whether the client is waiting on the server, the server is
+//! waiting on the client, or a response is waiting to be consumed.
+//!
+//! Snapshots of a running connection are requested with [`Client::debug_snapshot`], and are answered by the connection
+//! even if it is stalled on other requests. A connection which is being driven manually can also be inspected directly
+//! with [`Connection::debug_snapshot`]. With the `with-serde-1` feature enabled, snapshots implement `serde::Serialize`
+//! so they can be included in diagnostic reports.
+//!
+//! [`Client::debug_snapshot`]: crate::Client::debug_snapshot
+//! [`Connection::debug_snapshot`]: crate::Connection::debug_snapshot
+
+#[cfg(feature = "with-serde-1")]
+use serde_1::Serialize;
+use std::time::Duration;
+
+/// A snapshot of the state of a connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde-1", derive(Serialize), serde(crate = "serde_1"))]
+pub struct ConnectionSnapshot {
+    pub(crate) state: ConnectionState,
+    pub(crate) requests: Vec<RequestSnapshot>,
+    pub(crate) copy_in: bool,
+    pub(crate) buffered_messages: usize,
+    pub(crate) read_buffer_len: usize,
+    pub(crate) write_buffer_len: usize,
+}
+
+impl ConnectionSnapshot {
+    /// Returns the state of the connection.
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Returns the requests which have been sent to the server and have not yet been fully responded to, in the order
+    /// they will be responded to.
+    ///
+    /// Requests which have been made by a `Client` but not yet picked up by the connection are not included.
+    pub fn requests(&self) -> &[RequestSnapshot] {
+        &self.requests
+    }
+
+    /// Returns whether the connection is in the middle of streaming data from a `COPY ... FROM STDIN` query to the
+    /// server.
+    pub fn copy_in(&self) -> bool {
+        self.copy_in
+    }
+
+    /// Returns the number of messages which have been received from the server but not yet delivered.
+    pub fn buffered_messages(&self) -> usize {
+        self.buffered_messages
+    }
+
+    /// Returns the number of bytes received from the server which have not yet been decoded.
+    pub fn read_buffer_len(&self) -> usize {
+        self.read_buffer_len
+    }
+
+    /// Returns the number of bytes waiting to be written to the server.
+    pub fn write_buffer_len(&self) -> usize {
+        self.write_buffer_len
+    }
+}
+
+/// The state of a connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde-1", derive(Serialize), serde(crate = "serde_1"))]
+#[non_exhaustive]
+pub enum ConnectionState {
+    /// The connection is processing requests.
+    Active,
+    /// The `Client` has been dropped, and the connection will close once outstanding requests have completed.
+    Terminating,
+    /// The connection is closing.
+    Closing,
+}
+
+/// A snapshot of a request which is waiting on a response from the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde-1", derive(Serialize), serde(crate = "serde_1"))]
+pub struct RequestSnapshot {
+    pub(crate) age: Option<Duration>,
+    pub(crate) state: ResponseState,
+    pub(crate) batches_delivered: u64,
+}
+
+impl RequestSnapshot {
+    /// Returns the time since the connection picked up the request.
+    ///
+    /// This is not available on `wasm32` targets.
+    pub fn age(&self) -> Option<Duration> {
+        self.age
+    }
+
+    /// Returns the state of the response to the request.
+    pub fn state(&self) -> ResponseState {
+        self.state
+    }
+
+    /// Returns the number of batches of messages of the response which have been delivered to the `Client`.
+    pub fn batches_delivered(&self) -> u64 {
+        self.batches_delivered
+    }
+}
+
+/// The state of the response to a request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde-1", derive(Serialize), serde(crate = "serde_1"))]
+#[non_exhaustive]
+pub enum ResponseState {
+    /// No part of the response has been received from the server yet.
+    Waiting,
+    /// Part of the response has been delivered, and the rest is expected from the server.
+    Receiving,
+    /// Part of the response has been received, but the `Client` has not consumed the messages already delivered to it.
+    Blocked,
+    /// The `Client` is no longer interested in the response, and its remaining messages will be discarded.
+    Abandoned,
+}
@@ -1,6 +1,8 @@
 use crate::client::InnerClient;
 use crate::codec::FrontendMessage;
+use crate::config::SqlRedaction;
 use crate::connection::RequestMessages;
+use crate::transform::ColumnTransform;
 use crate::types::Type;
 use postgres_protocol::message::frontend;
 use std::fmt;
@@ -9,8 +11,13 @@ use std::sync::{Arc, Weak};
 struct StatementInner {
     client: Weak<InnerClient>,
     name: String,
+    query: Arc<str>,
+    /// The redaction policy applied when the query is reported.
+    sql_redaction: SqlRedaction,
     params: Vec<Type>,
     columns: Vec<Column>,
+    /// The transforms applied to each column, which is empty if none apply.
+    transforms: Vec<Option<ColumnTransform>>,
 }
 
 impl Drop for StatementInner {
@@ -20,6 +27,7 @@ impl Drop for StatementInner {
             return;
         }
         if let Some(client) = self.client.upgrade() {
+            client.prune_shared_statements(&self.query);
             let buf = client.with_buf(|buf| {
                 frontend::close(b'S', &self.name, buf).unwrap();
                 frontend::sync(buf);
@@ -40,23 +48,43 @@ impl Statement {
     pub(crate) fn new(
         inner: &Arc<InnerClient>,
         name: String,
+        query: &str,
         params: Vec<Type>,
         columns: Vec<Column>,
     ) -> Statement {
+        let transforms = resolve_transforms(inner, &columns);
+
         Statement(Arc::new(StatementInner {
             client: Arc::downgrade(inner),
             name,
+            query: Arc::from(query),
+            // the policy is only applied if the query is reported, which is rare
+            sql_redaction: inner.sql_redaction(),
             params,
             columns,
+            transforms,
         }))
     }
 
-    pub(crate) fn unnamed(params: Vec<Type>, columns: Vec<Column>) -> Statement {
+    pub(crate) fn downgrade(&self) -> WeakStatement {
+        WeakStatement(Arc::downgrade(&self.0))
+    }
+
+    pub(crate) fn unnamed(
+        inner: &InnerClient,
+        params: Vec<Type>,
+        columns: Vec<Column>,
+    ) -> Statement {
+        let transforms = resolve_transforms(inner, &columns);
+
         Statement(Arc::new(StatementInner {
             client: Weak::new(),
             name: String::new(),
+            query: Arc::from(""),
+            sql_redaction: SqlRedaction::Omit,
             params,
             columns,
+            transforms,
         }))
     }
 
@@ -65,6 +93,19 @@ impl Statement {
         &self.0.name
     }
 
+    /// Returns the statement's query, as permitted by the client's redaction policy.
+    pub(crate) fn redacted_query(&self) -> Option<String> {
+        self.0.sql_redaction.apply(&self.0.query)
+    }
+
+    pub(crate) fn has_transforms(&self) -> bool {
+        !self.0.transforms.is_empty()
+    }
+
+    pub(crate) fn column_transform(&self, idx: usize) -> Option<&ColumnTransform> {
+        self.0.transforms.get(idx)?.as_ref()
+    }
+
     /// Returns the expected types of the statement's parameters.
     pub fn params(&self) -> &[Type] {
         &self.0.params
@@ -76,6 +117,22 @@ impl Statement {
     }
 }
 
+/// Resolves the transforms which apply to each column, returning an empty list if none do.
+fn resolve_transforms(inner: &InnerClient, columns: &[Column]) -> Vec<Option<ColumnTransform>> {
+    let transforms = columns
+        .iter()
+        .map(|column| {
+            let transform = inner.value_transforms().resolve(column)?;
+            Some(ColumnTransform::new(transform, column))
+        })
+        .collect::<Vec<_>>();
+    if transforms.iter().any(Option::is_some) {
+        transforms
+    } else {
+        vec![]
+    }
+}
+
 impl std::fmt::Debug for Statement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         f.debug_struct("Statement")
@@ -86,6 +143,19 @@ impl std::fmt::Debug for Statement {
     }
 }
 
+/// A reference to a prepared statement which does not keep it alive.
+pub(crate) struct WeakStatement(Weak<StatementInner>);
+
+impl WeakStatement {
+    pub(crate) fn upgrade(&self) -> Option<Statement> {
+        self.0.upgrade().map(Statement)
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.0.strong_count() == 0
+    }
+}
+
 /// Information about a column of a query.
 pub struct Column {
     pub(crate) name: String,
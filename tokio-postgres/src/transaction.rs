@@ -5,11 +5,12 @@ use crate::query::RowStream;
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 use crate::tls::TlsConnect;
+use crate::transform::{TransformTarget, TransformedParam};
 use crate::types::{BorrowToSql, ToSql, Type};
 #[cfg(feature = "runtime")]
 use crate::Socket;
 use crate::{
-    bind, query, slice_iter, CancelToken, Client, CopyInSink, Error, Portal, Row,
+    bind, query, slice_iter, CancelToken, Client, CopyInSink, Error, FromRow, Portal, Row,
     SimpleQueryMessage, Statement, ToStatement,
 };
 use bytes::Buf;
@@ -190,6 +191,35 @@ impl<'a> Transaction<'a> {
         self.client.execute_raw(statement, params).await
     }
 
+    /// Like `Client::execute_returning`.
+    pub async fn execute_returning<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.client.execute_returning(statement, params).await
+    }
+
+    /// Like `Client::execute_returning_with_capacity`.
+    pub async fn execute_returning_with_capacity<R, T>(
+        &self,
+        statement: &T,
+        params: &[&(dyn ToSql + Sync)],
+        capacity: usize,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.client
+            .execute_returning_with_capacity(statement, params, capacity)
+            .await
+    }
+
     /// Binds a statement to a set of parameters, creating a `Portal` which can be incrementally queried.
     ///
     /// Portals only last for the duration of the transaction in which they are created, and can only be used on the
@@ -272,6 +302,18 @@ impl<'a> Transaction<'a> {
         self.client.batch_execute(query).await
     }
 
+    /// Like `Client::transform_param`.
+    pub fn transform_param<'b, T>(
+        &self,
+        target: T,
+        value: &'b (dyn ToSql + Sync),
+    ) -> TransformedParam<'b>
+    where
+        T: Into<TransformTarget>,
+    {
+        self.client.transform_param(target, value)
+    }
+
     /// Like `Client::cancel_token`.
     pub fn cancel_token(&self) -> CancelToken {
         self.client.cancel_token()
@@ -3,7 +3,7 @@ use postgres_protocol::message::frontend;
 use crate::{codec::FrontendMessage, connection::RequestMessages, Client, Error, Transaction};
 
 /// The isolation level of a database transaction.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum IsolationLevel {
     /// Equivalent to `ReadCommitted`.
@@ -21,22 +21,24 @@ pub enum IsolationLevel {
     Serializable,
 }
 
-/// A builder for database transactions.
-pub struct TransactionBuilder<'a> {
-    client: &'a mut Client,
+/// A reusable set of transaction settings.
+///
+/// Options can be applied to a [`TransactionBuilder`], passed to [`Client::transaction_with_options`], or configured
+/// as the defaults for every transaction started by a client via [`Config::transaction_options`]. Settings which are
+/// left unspecified use the server's defaults.
+///
+/// [`Config::transaction_options`]: crate::Config::transaction_options
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct TransactionOptions {
     isolation_level: Option<IsolationLevel>,
     read_only: Option<bool>,
     deferrable: Option<bool>,
 }
 
-impl<'a> TransactionBuilder<'a> {
-    pub(crate) fn new(client: &'a mut Client) -> TransactionBuilder<'a> {
-        TransactionBuilder {
-            client,
-            isolation_level: None,
-            read_only: None,
-            deferrable: None,
-        }
+impl TransactionOptions {
+    /// Creates a new set of options with every setting left unspecified.
+    pub fn new() -> TransactionOptions {
+        TransactionOptions::default()
     }
 
     /// Sets the isolation level of the transaction.
@@ -45,12 +47,22 @@ impl<'a> TransactionBuilder<'a> {
         self
     }
 
+    /// Gets the isolation level of the transaction, if one has been specified.
+    pub fn get_isolation_level(&self) -> Option<IsolationLevel> {
+        self.isolation_level
+    }
+
     /// Sets the access mode of the transaction.
     pub fn read_only(mut self, read_only: bool) -> Self {
         self.read_only = Some(read_only);
         self
     }
 
+    /// Gets the access mode of the transaction, if one has been specified.
+    pub fn get_read_only(&self) -> Option<bool> {
+        self.read_only
+    }
+
     /// Sets the deferrability of the transaction.
     ///
     /// If the transaction is also serializable and read only, creation of the transaction may block, but when it
@@ -61,6 +73,65 @@ impl<'a> TransactionBuilder<'a> {
         self
     }
 
+    /// Gets the deferrability of the transaction, if one has been specified.
+    pub fn get_deferrable(&self) -> Option<bool> {
+        self.deferrable
+    }
+
+    /// Returns these options with any unspecified settings taken from `defaults`.
+    fn or(self, defaults: TransactionOptions) -> TransactionOptions {
+        TransactionOptions {
+            isolation_level: self.isolation_level.or(defaults.isolation_level),
+            read_only: self.read_only.or(defaults.read_only),
+            deferrable: self.deferrable.or(defaults.deferrable),
+        }
+    }
+}
+
+/// A builder for database transactions.
+///
+/// The builder starts out with the client's default [`TransactionOptions`].
+pub struct TransactionBuilder<'a> {
+    client: &'a mut Client,
+    options: TransactionOptions,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    pub(crate) fn new(client: &'a mut Client) -> TransactionBuilder<'a> {
+        let options = client.transaction_options();
+        TransactionBuilder { client, options }
+    }
+
+    /// Applies a set of options to the transaction.
+    ///
+    /// Settings specified by `options` override those already set on the builder; the rest are left unchanged.
+    pub fn options(mut self, options: TransactionOptions) -> Self {
+        self.options = options.or(self.options);
+        self
+    }
+
+    /// Sets the isolation level of the transaction.
+    pub fn isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.options = self.options.isolation_level(isolation_level);
+        self
+    }
+
+    /// Sets the access mode of the transaction.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.options = self.options.read_only(read_only);
+        self
+    }
+
+    /// Sets the deferrability of the transaction.
+    ///
+    /// If the transaction is also serializable and read only, creation of the transaction may block, but when it
+    /// completes the transaction is able to run with less overhead and a guarantee that it will not be aborted due to
+    /// serialization failure.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.options = self.options.deferrable(deferrable);
+        self
+    }
+
     /// Begins the transaction.
     ///
     /// The transaction will roll back by default - use the `commit` method to commit it.
@@ -68,7 +139,7 @@ impl<'a> TransactionBuilder<'a> {
         let mut query = "START TRANSACTION".to_string();
         let mut first = true;
 
-        if let Some(level) = self.isolation_level {
+        if let Some(level) = self.options.isolation_level {
             first = false;
 
             query.push_str(" ISOLATION LEVEL ");
@@ -81,7 +152,7 @@ impl<'a> TransactionBuilder<'a> {
             query.push_str(level);
         }
 
-        if let Some(read_only) = self.read_only {
+        if let Some(read_only) = self.options.read_only {
             if !first {
                 query.push(',');
             }
@@ -95,7 +166,7 @@ impl<'a> TransactionBuilder<'a> {
             query.push_str(s);
         }
 
-        if let Some(deferrable) = self.deferrable {
+        if let Some(deferrable) = self.options.deferrable {
             if !first {
                 query.push(',');
             }
@@ -0,0 +1,205 @@
+//! Transformations of column values and query parameters.
+//!
+//! A [`ValueTransform`] sits between the wire format and the `FromSql` and `ToSql` implementations of a value. This
+//! allows layers such as client-side encryption to be applied transparently, without wrapping every type stored in an
+//! affected column.
+//!
+//! Transforms are registered for a [`TransformTarget`] with [`Config::value_transform`]. Values of matching columns
+//! are decoded automatically when rows are returned by prepared statements and by `query_typed`. Rows returned by
+//! `simple_query` hold the text representation of each value as sent by the server, and are never transformed.
+//! Postgres does not report which column a query parameter will be compared to or stored in, so parameters are
+//! transformed explicitly by wrapping them with [`Client::transform_param`].
+//!
+//! [`Config::value_transform`]: crate::Config::value_transform
+//! [`Client::transform_param`]: crate::Client::transform_param
+
+use crate::types::{to_sql_checked, Format, IsNull, ToSql, Type};
+use crate::Column;
+use bytes::BytesMut;
+use postgres_protocol::Oid;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+/// A transformation applied to the serialized values of a column.
+pub trait ValueTransform: fmt::Debug + Sync + Send {
+    /// Returns the type that values of a column of type `ty` are serialized and deserialized as.
+    ///
+    /// Defaults to `ty` itself. A transform which stores values in a different form, such as encrypting them into a
+    /// `BYTEA` column, should return the type of the original values.
+    fn value_type(&self, ty: &Type) -> Type {
+        ty.clone()
+    }
+
+    /// Transforms the raw value of a column received from the server before it is deserialized.
+    ///
+    /// `NULL` values are not passed to the transform.
+    fn decode(&self, column: &Column, raw: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>>;
+
+    /// Transforms a serialized parameter of type `ty` before it is sent to the server.
+    ///
+    /// `NULL` values are not passed to the transform.
+    fn encode(&self, ty: &Type, raw: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Sync + Send>>;
+}
+
+/// The columns a [`ValueTransform`] applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransformTarget {
+    /// A specific column of a table, identified by the OID of the table and the column's attribute number.
+    Column {
+        /// The OID of the table.
+        table_oid: Oid,
+        /// The attribute number of the column within the table.
+        column_id: i16,
+    },
+    /// Any column with the specified name.
+    ///
+    /// Transforms registered for a specific column take precedence.
+    Name(String),
+}
+
+impl From<&str> for TransformTarget {
+    fn from(name: &str) -> TransformTarget {
+        TransformTarget::Name(name.to_string())
+    }
+}
+
+impl From<String> for TransformTarget {
+    fn from(name: String) -> TransformTarget {
+        TransformTarget::Name(name)
+    }
+}
+
+/// The set of transforms registered with a `Config`.
+#[derive(Clone, Default)]
+pub(crate) struct ValueTransforms(HashMap<TransformTarget, Arc<dyn ValueTransform>>);
+
+impl ValueTransforms {
+    pub(crate) fn insert(&mut self, target: TransformTarget, transform: Arc<dyn ValueTransform>) {
+        self.0.insert(target, transform);
+    }
+
+    pub(crate) fn get(&self, target: &TransformTarget) -> Option<&Arc<dyn ValueTransform>> {
+        self.0.get(target)
+    }
+
+    pub(crate) fn resolve(&self, column: &Column) -> Option<&Arc<dyn ValueTransform>> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let by_id = match (column.table_oid(), column.column_id()) {
+            (Some(table_oid), Some(column_id)) => self.get(&TransformTarget::Column {
+                table_oid,
+                column_id,
+            }),
+            _ => None,
+        };
+        by_id.or_else(|| self.get(&TransformTarget::Name(column.name().to_string())))
+    }
+}
+
+// Transforms can't be compared themselves, so registrations are equal if they refer to the same instances.
+impl PartialEq for ValueTransforms {
+    fn eq(&self, other: &ValueTransforms) -> bool {
+        self.0.len() == other.0.len()
+            && self.0.iter().all(|(target, transform)| {
+                other.0.get(target).is_some_and(|other| {
+                    Arc::as_ptr(transform) as *const () == Arc::as_ptr(other) as *const ()
+                })
+            })
+    }
+}
+
+impl Eq for ValueTransforms {}
+
+impl fmt::Debug for ValueTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter()).finish()
+    }
+}
+
+/// The transform applied to a column of a statement.
+pub(crate) struct ColumnTransform {
+    pub(crate) transform: Arc<dyn ValueTransform>,
+    /// The type values of the column are deserialized as.
+    pub(crate) type_: Type,
+}
+
+impl ColumnTransform {
+    pub(crate) fn new(transform: &Arc<dyn ValueTransform>, column: &Column) -> ColumnTransform {
+        ColumnTransform {
+            transform: transform.clone(),
+            type_: transform.value_type(column.type_()),
+        }
+    }
+}
+
+/// A query parameter which is transformed before being sent to the server.
+///
+/// Created by [`Client::transform_param`].
+///
+/// [`Client::transform_param`]: crate::Client::transform_param
+pub struct TransformedParam<'a> {
+    value: &'a (dyn ToSql + Sync),
+    transform: Option<Arc<dyn ValueTransform>>,
+}
+
+impl<'a> TransformedParam<'a> {
+    pub(crate) fn new(
+        value: &'a (dyn ToSql + Sync),
+        transform: Option<Arc<dyn ValueTransform>>,
+    ) -> TransformedParam<'a> {
+        TransformedParam { value, transform }
+    }
+}
+
+// The value is not included since hiding it is often the purpose of the transform.
+impl fmt::Debug for TransformedParam<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransformedParam")
+            .field("transform", &self.transform)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ToSql for TransformedParam<'_> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let transform = match &self.transform {
+            Some(transform) => transform,
+            None => return self.value.to_sql_checked(ty, out),
+        };
+
+        let mut raw = BytesMut::new();
+        match self
+            .value
+            .to_sql_checked(&transform.value_type(ty), &mut raw)?
+        {
+            IsNull::Yes => Ok(IsNull::Yes),
+            IsNull::No => {
+                out.extend_from_slice(&transform.encode(ty, &raw)?);
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    // the wrapped value is checked against the transformed type when serialized
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, ty: &Type) -> Format {
+        match &self.transform {
+            // transforms produce the binary representation of the server's type
+            Some(_) => Format::Binary,
+            None => self.value.encode_format(ty),
+        }
+    }
+
+    to_sql_checked!();
+}
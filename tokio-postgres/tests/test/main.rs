@@ -8,15 +8,19 @@ use futures_util::{
 use pin_project_lite::pin_project;
 use std::fmt::Write;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 use tokio::net::TcpStream;
 use tokio::time;
 use tokio_postgres::error::SqlState;
+use tokio_postgres::snapshot::{ConnectionState, ResponseState};
 use tokio_postgres::tls::{NoTls, NoTlsStream};
+use tokio_postgres::transform::{TransformTarget, ValueTransform};
 use tokio_postgres::types::{Kind, Type};
 use tokio_postgres::{
     AsyncMessage, Client, Config, Connection, Error, IsolationLevel, SimpleQueryMessage,
+    TransactionOptions,
 };
 
 mod binary_copy;
@@ -163,6 +167,67 @@ async fn pipelined_prepare() {
     assert_eq!(statement2.columns()[0].type_(), &Type::INT8);
 }
 
+#[tokio::test]
+async fn shared_statements() {
+    let socket = TcpStream::connect("127.0.0.1:5433").await.unwrap();
+    let mut config = "user=postgres".parse::<Config>().unwrap();
+    config.shared_statements(true);
+    let (client, connection) = config.connect_raw(socket, NoTls).await.unwrap();
+    tokio::spawn(connection.map(|r| r.unwrap()));
+    let other = client.clone();
+
+    let a = client.prepare("SELECT $1::INT").await.unwrap();
+    let b = other.prepare("SELECT $1::INT").await.unwrap();
+    assert_eq!(a.name(), b.name());
+
+    let c = other
+        .prepare_typed("SELECT $1::INT", &[Type::INT8])
+        .await
+        .unwrap();
+    assert_ne!(a.name(), c.name());
+
+    let row = other.query_one(&a, &[&1i32]).await.unwrap();
+    assert_eq!(row.get::<_, i32>(0), 1);
+    drop(row);
+
+    let name = a.name().to_string();
+    let count = "SELECT count(*) FROM pg_prepared_statements WHERE name = $1";
+
+    drop(a);
+    let row = client.query_one(count, &[&name]).await.unwrap();
+    assert_eq!(row.get::<_, i64>(0), 1);
+
+    drop(b);
+    let row = client.query_one(count, &[&name]).await.unwrap();
+    assert_eq!(row.get::<_, i64>(0), 0);
+
+    let d = other.prepare("SELECT $1::INT").await.unwrap();
+    assert_ne!(d.name(), name);
+    let e = client.prepare("SELECT $1::INT").await.unwrap();
+    assert_eq!(d.name(), e.name());
+}
+
+#[tokio::test]
+async fn cloned_client_shares_transaction() {
+    let mut client = connect("user=postgres").await;
+    let other = client.clone();
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT)")
+        .await
+        .unwrap();
+
+    let transaction = client.transaction().await.unwrap();
+    other
+        .execute("INSERT INTO foo (id) VALUES (1)", &[])
+        .await
+        .unwrap();
+    transaction.rollback().await.unwrap();
+
+    let rows = other.query("SELECT id FROM foo", &[]).await.unwrap();
+    assert_eq!(rows.len(), 0);
+}
+
 #[tokio::test]
 async fn insert_select() {
     let client = connect("user=postgres").await;
@@ -187,6 +252,47 @@ async fn insert_select() {
     assert_eq!(rows[1].get::<_, &str>(1), "bob");
 }
 
+#[tokio::test]
+async fn execute_returning() {
+    let client = connect("user=postgres").await;
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id SERIAL, name TEXT)")
+        .await
+        .unwrap();
+
+    let ids: Vec<(i32,)> = client
+        .execute_returning(
+            "INSERT INTO foo (name) VALUES ($1), ($2), ($3) RETURNING id",
+            &[&"alice", &"bob", &"carol"],
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids, [(1,), (2,), (3,)]);
+
+    let rows: Vec<(i32, String)> = client
+        .execute_returning_with_capacity(
+            "UPDATE foo SET name = upper(name) WHERE id > $1 RETURNING id, name",
+            &[&1i32],
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows, [(2, "BOB".to_string()), (3, "CAROL".to_string())]);
+
+    let rows: Vec<(i32,)> = client
+        .execute_returning("DELETE FROM foo WHERE id > $1 RETURNING id", &[&3i32])
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+
+    let err = client
+        .execute_returning::<(String,), _>("SELECT id FROM foo", &[])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("error deserializing column 0"));
+}
+
 #[tokio::test]
 async fn custom_enum() {
     let client = connect("user=postgres").await;
@@ -560,6 +666,195 @@ async fn transaction_builder() {
     assert_eq!(rows[0].get::<_, &str>(0), "steven");
 }
 
+#[tokio::test]
+async fn parameter_count_mismatch() {
+    let client = connect("user=postgres").await;
+
+    let stmt = client
+        .prepare("SELECT $1::INT4, $2::TEXT, 'secret'")
+        .await
+        .unwrap();
+    let err = client.query(&stmt, &[&1i32]).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "expected 2 parameters but got 1 for statement {} with parameter types [int4, text]: \
+             SELECT $1::INT4, $2::TEXT, ?",
+            stmt.name(),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn transaction_with_options() {
+    let socket = TcpStream::connect("127.0.0.1:5433").await.unwrap();
+    let (mut client, connection) = Config::new()
+        .user("postgres")
+        .transaction_options(
+            TransactionOptions::new().isolation_level(IsolationLevel::RepeatableRead),
+        )
+        .connect_raw(socket, NoTls)
+        .await
+        .unwrap();
+    tokio::spawn(connection.map(|r| r.unwrap()));
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT)")
+        .await
+        .unwrap();
+
+    let transaction = client.transaction().await.unwrap();
+    let row = transaction
+        .query_one("SHOW transaction_isolation", &[])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, &str>(0), "repeatable read");
+    transaction.rollback().await.unwrap();
+
+    let options = TransactionOptions::new().read_only(true);
+    let transaction = client
+        .build_transaction()
+        .options(options)
+        .start()
+        .await
+        .unwrap();
+    let row = transaction
+        .query_one(
+            "SELECT current_setting('transaction_isolation'), current_setting('transaction_read_only')",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, &str>(0), "repeatable read");
+    assert_eq!(row.get::<_, &str>(1), "on");
+    transaction.rollback().await.unwrap();
+
+    let options = TransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+    let isolation = client
+        .transaction_with_options(options, |transaction| {
+            Box::pin(async move {
+                transaction
+                    .execute("INSERT INTO foo (id) VALUES (1)", &[])
+                    .await?;
+                let row = transaction
+                    .query_one("SHOW transaction_isolation", &[])
+                    .await?;
+                Ok::<_, Error>(row.get::<_, String>(0))
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(isolation, "serializable");
+
+    let err = client
+        .transaction_with_options(TransactionOptions::new(), |transaction| {
+            Box::pin(async move {
+                transaction
+                    .execute("INSERT INTO foo (id) VALUES (2)", &[])
+                    .await?;
+                transaction.execute("SELECT 1 / 0", &[]).await
+            })
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::DIVISION_BY_ZERO));
+
+    let rows = client.query("SELECT id FROM foo", &[]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+}
+
+#[derive(Debug)]
+struct XorTransform(u8);
+
+impl XorTransform {
+    fn apply(&self, raw: &[u8]) -> Vec<u8> {
+        raw.iter().map(|b| b ^ self.0).collect()
+    }
+}
+
+impl ValueTransform for XorTransform {
+    fn value_type(&self, _: &Type) -> Type {
+        Type::TEXT
+    }
+
+    fn decode(
+        &self,
+        _: &tokio_postgres::Column,
+        raw: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(self.apply(raw))
+    }
+
+    fn encode(
+        &self,
+        ty: &Type,
+        raw: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        assert_eq!(*ty, Type::BYTEA);
+        Ok(self.apply(raw))
+    }
+}
+
+#[tokio::test]
+async fn value_transform() {
+    let socket = TcpStream::connect("127.0.0.1:5433").await.unwrap();
+    let (client, connection) = Config::new()
+        .user("postgres")
+        .value_transform(
+            TransformTarget::Name("secret".to_string()),
+            Arc::new(XorTransform(0x2a)),
+        )
+        .connect_raw(socket, NoTls)
+        .await
+        .unwrap();
+    tokio::spawn(connection.map(|r| r.unwrap()));
+
+    client
+        .batch_execute("CREATE TEMPORARY TABLE foo (id INT, secret BYTEA)")
+        .await
+        .unwrap();
+
+    let secret = "hunter2";
+    client
+        .execute(
+            "INSERT INTO foo (id, secret) VALUES (1, $1), (2, $2)",
+            &[
+                &client.transform_param("secret", &secret),
+                &client.transform_param("secret", &None::<&str>),
+            ],
+        )
+        .await
+        .unwrap();
+
+    let rows = client
+        .query("SELECT id, secret, secret AS raw FROM foo ORDER BY id", &[])
+        .await
+        .unwrap();
+    assert_eq!(rows[0].get::<_, &str>("secret"), secret);
+    assert_eq!(
+        rows[0].get::<_, &[u8]>("raw"),
+        XorTransform(0x2a).apply(secret.as_bytes()),
+    );
+    assert_eq!(rows[1].get::<_, Option<String>>("secret"), None);
+
+    let err = rows[0].try_get::<_, &[u8]>("secret").unwrap_err();
+    assert!(err.to_string().contains("error deserializing column 1"));
+
+    let rows = client
+        .query_typed(
+            "SELECT secret, secret AS raw FROM foo WHERE id = $1",
+            &[(&1i32, Type::INT4)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows[0].get::<_, &str>("secret"), secret);
+    assert_eq!(
+        rows[0].get::<_, &[u8]>("raw"),
+        XorTransform(0x2a).apply(secret.as_bytes()),
+    );
+}
+
 #[tokio::test]
 async fn copy_in() {
     let client = connect("user=postgres").await;
@@ -768,6 +1063,79 @@ async fn notifications() {
     assert_eq!(notifications[1].payload(), "world");
 }
 
+#[tokio::test]
+async fn debug_snapshot() {
+    let (client, mut connection) = connect_raw("user=postgres").await.unwrap();
+
+    let snapshot = connection.debug_snapshot();
+    assert_eq!(snapshot.state(), ConnectionState::Active);
+    assert!(snapshot.requests().is_empty());
+    assert!(!snapshot.copy_in());
+
+    // polling the query once hands its request to the connection, and polling the connection once sends it
+    let query = client.simple_query("SELECT pg_sleep(0.1)");
+    pin_mut!(query);
+    assert!(query.as_mut().now_or_never().is_none());
+    let abandoned = client.simple_query("SELECT 1");
+    assert!(Box::pin(abandoned).now_or_never().is_none());
+    future::poll_fn(|cx| {
+        assert!(connection.poll_message(cx).is_pending());
+        Poll::Ready(())
+    })
+    .await;
+
+    let snapshot = connection.debug_snapshot();
+    assert_eq!(snapshot.requests().len(), 2);
+    assert_eq!(snapshot.requests()[0].state(), ResponseState::Waiting);
+    assert_eq!(snapshot.requests()[0].batches_delivered(), 0);
+    assert!(snapshot.requests()[0].age().is_some());
+    assert_eq!(snapshot.requests()[1].state(), ResponseState::Abandoned);
+    assert_eq!(snapshot.write_buffer_len(), 0);
+
+    future::poll_fn(|cx| {
+        while let Poll::Ready(Some(message)) = connection.poll_message(cx) {
+            message.unwrap();
+        }
+        query.as_mut().poll(cx)
+    })
+    .await
+    .unwrap();
+
+    let snapshot = connection.debug_snapshot();
+    assert!(snapshot.requests().len() <= 1);
+    assert!(snapshot
+        .requests()
+        .iter()
+        .all(|r| r.state() == ResponseState::Abandoned));
+}
+
+#[tokio::test]
+async fn client_debug_snapshot() {
+    let client = connect("user=postgres").await;
+
+    let snapshot = client.debug_snapshot().await.unwrap();
+    assert_eq!(snapshot.state(), ConnectionState::Active);
+    assert!(snapshot.requests().is_empty());
+
+    let query = client.simple_query("SELECT pg_sleep(0.1)");
+    pin_mut!(query);
+    assert!(query.as_mut().now_or_never().is_none());
+
+    // the connection may answer the first snapshot request before it picks up the query
+    let snapshot = loop {
+        let snapshot = client.debug_snapshot().await.unwrap();
+        if !snapshot.requests().is_empty() {
+            break snapshot;
+        }
+    };
+    assert_eq!(snapshot.requests().len(), 1);
+    assert_eq!(snapshot.requests()[0].state(), ResponseState::Waiting);
+
+    query.await.unwrap();
+    let snapshot = client.debug_snapshot().await.unwrap();
+    assert!(snapshot.requests().is_empty());
+}
+
 #[tokio::test]
 async fn query_portal() {
     let mut client = connect("user=postgres").await;